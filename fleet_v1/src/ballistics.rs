@@ -1,32 +1,70 @@
 use oort_api::prelude::*;
 
 
-use crate::settings;
+use crate::io::ShipIo;
+use crate::settings::Settings;
 
-pub fn get_angular_target_size(enemy_size: f64, target: Vec2) -> f64 { // get the size of a target on the periferal view of a ship (in radians)
-    return enemy_size / (target - position()).length();
+// Root finding stuff
+const INTERCEPT_SEARCH_STEPS: i32 = 100;
+
+pub struct Intercept {
+    pub time: f64, // bullet time of flight
+    pub aim_point: Vec2, // point to aim the gun at (bullets inherit the ship's velocity)
+    pub target_position: Vec2, // where the target will be hit
 }
 
-pub fn get_bullet_time(relative_position: Vec2, bullet_speed: f64) -> f64 { // Calculate the actual speed of bullets (taking into account ship velocity)
-    return relative_position.length() / (bullet_speed + velocity().dot(relative_position.normalize()));
+pub fn get_angular_target_size(io: &impl ShipIo, enemy_size: f64, target: Vec2) -> f64 { // get the size of a target on the periferal view of a ship (in radians)
+    return enemy_size / (target - io.position()).length();
 }
 
-pub fn lead(target: Vec2, target_velocity : Vec2, target_acceleration: Vec2, bullet_speed: f64, target_size: f64, origin_position: Vec2) -> Vec2 {
-    let mut target_projected = target;
-    loop { // repeatedly projecting with the new distance
-        let t = get_bullet_time(target_projected - origin_position, bullet_speed); // calculating time to encounter with given speed
-        let last_projection = target_projected;
-        target_projected = target + target_velocity * t + 0.5 * target_acceleration * t * t; // Calculating lead to target
-        let angular_target_size = get_angular_target_size(target_size, target_projected);
-                                                                                    
-        if (target_projected - last_projection).length() < angular_target_size * settings::PREDICTION_ACCURACY_FACTOR {
-            break;
+#[allow(clippy::too_many_arguments)]
+pub fn intercept(io: &impl ShipIo, settings: &Settings, target: Vec2, target_velocity : Vec2, target_acceleration: Vec2, bullet_speed: f64, target_size: f64, origin_position: Vec2) -> Option<Intercept> {
+    // Work in the frame of the fired bullet: it inherits our velocity, so only the bullet speed moves it away from the origin.
+    // A hit at time t means |relative_position(t)| = bullet_speed * t.
+    let relative_position = target - origin_position;
+    let relative_velocity = target_velocity - io.velocity();
+    let projected_relative = |t: f64| relative_position + relative_velocity * t + 0.5 * target_acceleration * t * t;
+
+    let time = if target_acceleration.length() * settings.max_intercept_time * settings.max_intercept_time < target_size * settings.prediction_accuracy_factor {
+        solve_constant_velocity(relative_position, relative_velocity, bullet_speed)?
+    } else {
+        // Quartic in t: step forward until the bullet first reaches the target, then bisect
+        let miss = |t: f64| projected_relative(t).length() - bullet_speed * t;
+        let step = settings.max_intercept_time / INTERCEPT_SEARCH_STEPS as f64;
+        let mut low = 0.0;
+        let mut high = (1..=INTERCEPT_SEARCH_STEPS).map(|i| i as f64 * step).find(|t| miss(*t) <= 0.0)?;
+        if high > step {
+            low = high - step;
+        }
+        while (high - low) * bullet_speed > target_size * settings.prediction_accuracy_factor {
+            let mid = 0.5 * (low + high);
+            if miss(mid) > 0.0 { low = mid; } else { high = mid; }
         }
+        high
+    };
+    if time > settings.max_intercept_time {
+        return None;
     }
-    let self_projected_relative = velocity() * get_bullet_time(target_projected - origin_position, bullet_speed);
-    return target_projected - self_projected_relative;
+    return Some(Intercept {
+        time,
+        aim_point: origin_position + projected_relative(time),
+        target_position: target + target_velocity * time + 0.5 * target_acceleration * time * time,
+    });
 }
 
-pub fn lead_from_self(target: Vec2, target_velocity : Vec2, target_acceleration: Vec2, bullet_speed: f64, target_size: f64) -> Vec2 {
-    return lead(target, target_velocity, target_acceleration, bullet_speed, target_size, position());
+fn solve_constant_velocity(relative_position: Vec2, relative_velocity: Vec2, bullet_speed: f64) -> Option<f64> { // smallest positive t of |p + v t| = s t
+    let a = relative_velocity.dot(relative_velocity) - bullet_speed * bullet_speed;
+    let b = 2.0 * relative_position.dot(relative_velocity);
+    let c = relative_position.dot(relative_position);
+    if a.abs() < 1e-9 { // bullet and target equally fast: linear
+        return if b < 0.0 { Some(-c / b) } else { None };
+    }
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    return [(-b - root) / (2.0 * a), (-b + root) / (2.0 * a)].into_iter()
+        .filter(|t| *t >= 0.0)
+        .min_by(|a, b| a.total_cmp(b));
 }
@@ -0,0 +1,109 @@
+#![allow(clippy::needless_return)]
+
+// Flattens the crate into one source file Oort accepts: every `mod x;` becomes `mod x { ... }`.
+//
+// usage: cargo run --bin bundle [-- <output file>]   (prints to stdout without an output file)
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const SKIPPED_MODULES: &[&str] = &[
+    "sim", // the simulator plays the game's side of oort_api, it has no place in a ship
+    "mock", // test support
+];
+
+fn main() -> ExitCode {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    match bundle(&src) {
+        Ok(bundled) => {
+            match std::env::args().nth(1) {
+                Some(output) => {
+                    if let Err(error) = fs::write(&output, bundled) {
+                        eprintln!("bundle: can't write {}: {}", output, error);
+                        return ExitCode::FAILURE;
+                    }
+                }
+                None => print!("{}", bundled),
+            }
+            return ExitCode::SUCCESS;
+        }
+        Err(error) => {
+            eprintln!("bundle: {}", error);
+            return ExitCode::FAILURE;
+        }
+    }
+}
+
+fn bundle(src: &Path) -> Result<String, String> {
+    let mut bundled = String::from("// Generated by `cargo run --bin bundle` from fleet_v1, edit the crate instead\n\n");
+    let mut modules = HashSet::new();
+    inline(&src.join("lib.rs"), src, 0, &mut bundled, &mut modules)?;
+
+    // every `use crate::x` has to point at a module that made it into the bundle
+    for line in bundled.lines() {
+        if let Some(path) = line.trim().strip_prefix("use crate::") {
+            let module = path.split(|c: char| !(c.is_alphanumeric() || c == '_')).next().unwrap_or("");
+            if !modules.contains(module) {
+                return Err(format!("`{}` uses a module that isn't bundled", line.trim()));
+            }
+        }
+    }
+    return Ok(bundled);
+}
+
+// copies `file` into `out`, replacing `mod x;` declarations with the module's source (looked for in `dir`)
+fn inline(file: &Path, dir: &Path, depth: usize, out: &mut String, modules: &mut HashSet<String>) -> Result<(), String> {
+    let source = fs::read_to_string(file).map_err(|error| format!("can't read {}: {}", file.display(), error))?;
+    let indent = "    ".repeat(depth);
+
+    let mut attributes = Vec::new(); // held until we know whether the item they belong to is kept
+    for line in source.lines() {
+        if line.trim().starts_with("#[") {
+            attributes.push(line);
+            continue;
+        }
+        let declaration = module_declaration(line);
+        if depth == 0 && declaration.is_some_and(|(_, name)| SKIPPED_MODULES.contains(&name)) {
+            attributes.clear();
+            continue;
+        }
+        for attribute in attributes.drain(..) {
+            out.push_str(&format!("{}{}\n", indent, attribute));
+        }
+        let Some((visibility, name)) = declaration else {
+            if line.is_empty() {
+                out.push('\n');
+            } else {
+                out.push_str(&format!("{}{}\n", indent, line));
+            }
+            continue;
+        };
+        if depth == 0 {
+            modules.insert(name.to_string());
+        }
+
+        let path = module_file(dir, name).ok_or_else(|| format!("can't find the file of module `{}` in {}", name, dir.display()))?;
+        out.push_str(&format!("{}{}mod {} {{\n", indent, visibility, name));
+        inline(&path, &dir.join(name), depth + 1, out, modules)?;
+        out.push_str(&format!("{}}}\n", indent));
+    }
+    return Ok(());
+}
+
+fn module_declaration(line: &str) -> Option<(&str, &str)> { // `pub mod x;` -> ("pub ", "x")
+    let trimmed = line.trim();
+    let (visibility, rest) = match trimmed.strip_prefix("pub ") {
+        Some(rest) => ("pub ", rest),
+        None => ("", trimmed),
+    };
+    let name = rest.strip_prefix("mod ")?.strip_suffix(';')?.trim();
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+    return Some((visibility, name));
+}
+
+fn module_file(dir: &Path, name: &str) -> Option<PathBuf> {
+    return [dir.join(format!("{}.rs", name)), dir.join(name).join("mod.rs")].into_iter().find(|path| path.is_file());
+}
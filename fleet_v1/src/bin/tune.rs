@@ -0,0 +1,273 @@
+#![allow(clippy::needless_return)]
+
+// Tunes the settings on headless engagements: a tuned team 0 fights a team 1 flying the defaults.
+// Every candidate meets the same seeded engagements, random search explores, hill climbing refines the best,
+// and the winner is scored again on engagements it was never tuned on.
+//
+// usage: cargo run --release --bin tune [-- --seed <n> --iterations <n> --games <n>]
+use std::process::ExitCode;
+
+use fleet_v1::settings::{ClassSettings, Settings};
+use fleet_v1::sim::Simulation;
+use oort_api::prelude::oorandom::Rand64;
+use oort_api::prelude::*;
+
+const USAGE: &str = "usage: tune [--seed <n>] [--iterations <n>] [--games <n>]";
+
+// Scoring stuff
+const TIME_BONUS: f64 = 0.25; // extra score for a kill at the first tick, falling to 0 at the time limit
+const DAMAGE_PENALTY: f64 = 0.25; // score lost for ending the engagement with no health left
+const Z_95: f64 = 1.96;
+
+// Search stuff
+const RANDOM_SHARE: f64 = 0.5; // of the iterations spent on random search before hill climbing
+const INITIAL_STEP: f64 = 0.25; // hill climbing step, as a share of each parameter's range
+const FINAL_STEP: f64 = 0.02;
+const VALIDATION_SEED_OFFSET: u64 = 1_000_000; // validation games never overlap the tuning games
+
+struct Parameter {
+    name: &'static str,
+    min: f64,
+    max: f64,
+    get: fn(&Settings) -> f64,
+    set: fn(&mut Settings, f64),
+}
+
+fn parameters() -> Vec<Parameter> { // the continuous settings worth searching over
+    return vec![
+        Parameter { name: "n_factor", min: 2.0, max: 6.0, get: |s| s.n_factor, set: |s, v| s.n_factor = v },
+        Parameter { name: "missile_fire_dist", min: 1000.0, max: 10000.0, get: |s| s.missile_fire_dist, set: |s, v| s.missile_fire_dist = v },
+        Parameter { name: "missile_fire_angle", min: 0.0, max: PI / 2.0, get: |s| s.missile_fire_angle, set: |s, v| s.missile_fire_angle = v },
+        Parameter { name: "coast_steering_acceleration", min: 0.0, max: 100.0, get: |s| s.coast_steering_acceleration, set: |s, v| s.coast_steering_acceleration = v },
+        Parameter { name: "max_forward_dist", min: 1000.0, max: 10000.0, get: |s| s.max_forward_dist, set: |s, v| s.max_forward_dist = v },
+        Parameter { name: "min_lateral_dist", min: 100.0, max: 2000.0, get: |s| s.min_lateral_dist, set: |s, v| s.min_lateral_dist = v },
+        Parameter { name: "target_scan_range_mult", min: 0.0, max: 0.1, get: |s| s.target_scan_range_mult, set: |s, v| s.target_scan_range_mult = v },
+        Parameter { name: "target_d_v_mult", min: 0.0, max: 0.2, get: |s| s.target_d_v_mult, set: |s, v| s.target_d_v_mult = v },
+        Parameter { name: "target_switch_ratio", min: 1.0, max: 3.0, get: |s| s.target_switch_ratio, set: |s, v| s.target_switch_ratio = v },
+    ];
+}
+
+// -- engagements --
+
+#[derive(Clone, Copy)]
+enum Scenario {
+    FighterDuel,
+    FrigateVsCruiser,
+    MissileVsTarget,
+}
+
+const SCENARIOS: [Scenario; 3] = [Scenario::FighterDuel, Scenario::FrigateVsCruiser, Scenario::MissileVsTarget];
+
+impl Scenario {
+    fn name(&self) -> &'static str {
+        return match self {
+            Scenario::FighterDuel => "fighter vs fighter",
+            Scenario::FrigateVsCruiser => "frigate vs cruiser",
+            Scenario::MissileVsTarget => "missile vs target",
+        };
+    }
+
+    fn max_ticks(&self) -> u32 {
+        return match self {
+            Scenario::FighterDuel => 60 * 60,
+            Scenario::FrigateVsCruiser => 60 * 120,
+            Scenario::MissileVsTarget => 60 * 20,
+        };
+    }
+
+    fn classes(&self) -> (Class, Class) { // (tuned, opponent)
+        return match self {
+            Scenario::FighterDuel => (Class::Fighter, Class::Fighter),
+            Scenario::FrigateVsCruiser => (Class::Frigate, Class::Cruiser),
+            Scenario::MissileVsTarget => (Class::Missile, Class::Target),
+        };
+    }
+}
+
+struct Outcome {
+    won: bool,
+    lost: bool,
+    seconds: f64,
+    damage: f64, // share of the tuned ship's health lost
+}
+
+impl Outcome {
+    fn score(&self, max_seconds: f64) -> f64 {
+        let result = if self.won { 1.0 } else if self.lost { 0.0 } else { 0.5 };
+        let time_bonus = if self.won { TIME_BONUS * (1.0 - self.seconds / max_seconds) } else { 0.0 };
+        return result + time_bonus - DAMAGE_PENALTY * self.damage;
+    }
+}
+
+fn engage(scenario: Scenario, settings: &Settings, seed: u64) -> Outcome {
+    let mut rng = Rand64::new(seed as u128);
+    let mut sim = Simulation::new(seed);
+    sim.set_team_settings(0, ClassSettings { default: *settings, overrides: Vec::new() });
+
+    // the opponent starts somewhere around us, pointed roughly our way
+    let (tuned_class, opponent_class) = scenario.classes();
+    let distance = 2000.0 + 3000.0 * rng.rand_float();
+    let bearing = TAU * rng.rand_float();
+    let opponent_position = vec2(distance, 0.0).rotate(bearing);
+    let tuned = sim.add_ship(tuned_class, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), bearing + (rng.rand_float() - 0.5));
+    let opponent_velocity = if opponent_class == Class::Target { vec2(0.0, 100.0 * (rng.rand_float() - 0.5)).rotate(bearing) } else { vec2(0.0, 0.0) };
+    let opponent = sim.add_ship(opponent_class, 1, opponent_position, opponent_velocity, bearing + PI + (rng.rand_float() - 0.5));
+
+    // a lone missile never counts as a team that won, so watch the two ships instead of asking for a winner
+    let alive = |sim: &Simulation, id: u32| sim.ship(id).is_some_and(|ship| ship.alive());
+    while sim.current_tick() < scenario.max_ticks() && alive(&sim, tuned) && alive(&sim, opponent) {
+        sim.tick();
+    }
+    let max_health = tuned_class.default_stats().max_health;
+    return Outcome {
+        won: !alive(&sim, opponent),
+        lost: alive(&sim, opponent) && !alive(&sim, tuned) && tuned_class != Class::Missile, // a missile running out of time or fuel is a miss, not a loss
+        seconds: sim.current_time(),
+        damage: sim.ship(tuned).map_or(1.0, |ship| (ship.damage_taken / max_health).min(1.0)),
+    };
+}
+
+// -- statistics --
+
+struct Evaluation {
+    scores: Vec<f64>,
+    outcomes: Vec<(usize, Outcome)>, // (scenario index, outcome)
+}
+
+impl Evaluation {
+    fn mean(&self) -> f64 {
+        return mean(&self.scores);
+    }
+
+    fn confidence(&self) -> f64 { // half width of the 95% interval of the mean
+        return confidence(&self.scores);
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    return values.iter().sum::<f64>() / values.len().max(1) as f64;
+}
+
+fn confidence(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return f64::INFINITY;
+    }
+    let mean = mean(values);
+    let variance = values.iter().map(|value| (value - mean) * (value - mean)).sum::<f64>() / (values.len() - 1) as f64;
+    return Z_95 * (variance / values.len() as f64).sqrt();
+}
+
+fn evaluate(settings: &Settings, games: u64, first_seed: u64) -> Evaluation {
+    let mut evaluation = Evaluation { scores: Vec::new(), outcomes: Vec::new() };
+    for (i, scenario) in SCENARIOS.iter().enumerate() {
+        for game in 0..games {
+            let outcome = engage(*scenario, settings, first_seed + game);
+            evaluation.scores.push(outcome.score(scenario.max_ticks() as f64 * TICK_LENGTH));
+            evaluation.outcomes.push((i, outcome));
+        }
+    }
+    return evaluation;
+}
+
+// -- search --
+
+fn random_settings(rng: &mut Rand64, parameters: &[Parameter]) -> Settings {
+    let mut settings = Settings::new();
+    for parameter in parameters {
+        (parameter.set)(&mut settings, parameter.min + (parameter.max - parameter.min) * rng.rand_float());
+    }
+    return settings;
+}
+
+fn neighbour(rng: &mut Rand64, parameters: &[Parameter], settings: &Settings, step: f64) -> Settings { // nudges one parameter
+    let mut neighbour = *settings;
+    let parameter = &parameters[rng.rand_range(0..parameters.len() as u64) as usize];
+    let nudge = (parameter.max - parameter.min) * step * (2.0 * rng.rand_float() - 1.0);
+    (parameter.set)(&mut neighbour, ((parameter.get)(settings) + nudge).clamp(parameter.min, parameter.max));
+    return neighbour;
+}
+
+fn search(seed: u64, iterations: u32, games: u64) -> (Settings, f64) {
+    let parameters = parameters();
+    let mut rng = Rand64::new(seed as u128);
+    let mut best = (Settings::new(), evaluate(&Settings::new(), games, seed).mean());
+
+    let random_iterations = (iterations as f64 * RANDOM_SHARE) as u32;
+    for iteration in 0..iterations {
+        let candidate = if iteration < random_iterations {
+            random_settings(&mut rng, &parameters)
+        } else {
+            let progress = (iteration - random_iterations) as f64 / (iterations - random_iterations).max(1) as f64;
+            neighbour(&mut rng, &parameters, &best.0, INITIAL_STEP + (FINAL_STEP - INITIAL_STEP) * progress)
+        };
+        let score = evaluate(&candidate, games, seed).mean();
+        if score > best.1 {
+            eprintln!("iteration {}: {:.3}", iteration, score);
+            best = (candidate, score);
+        }
+    }
+    return best;
+}
+
+// -- report --
+
+fn report(name: &str, evaluation: &Evaluation) {
+    println!("{}: score {:.3} ± {:.3} over {} games", name, evaluation.mean(), evaluation.confidence(), evaluation.scores.len());
+    for (i, scenario) in SCENARIOS.iter().enumerate() {
+        let outcomes: Vec<&Outcome> = evaluation.outcomes.iter().filter(|(index, _)| *index == i).map(|(_, outcome)| outcome).collect();
+        let wins: Vec<f64> = outcomes.iter().map(|outcome| if outcome.won { 1.0 } else { 0.0 }).collect();
+        let kill_times: Vec<f64> = outcomes.iter().filter(|outcome| outcome.won).map(|outcome| outcome.seconds).collect();
+        let damage: Vec<f64> = outcomes.iter().map(|outcome| outcome.damage).collect();
+        println!(
+            "  {:<20} win rate {:>5.1}% ± {:.1}%, time to kill {}, damage taken {:.1}% ± {:.1}%",
+            scenario.name(),
+            100.0 * mean(&wins),
+            100.0 * confidence(&wins).min(1.0),
+            if kill_times.is_empty() { "-".to_string() } else { format!("{:.1} s ± {:.1} s", mean(&kill_times), confidence(&kill_times).min(mean(&kill_times))) },
+            100.0 * mean(&damage),
+            100.0 * confidence(&damage).min(1.0),
+        );
+    }
+}
+
+fn parse_args() -> Result<(u64, u32, u64), String> { // (seed, iterations, games)
+    let (mut seed, mut iterations, mut games) = (1, 40, 8);
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let value = args.next().ok_or_else(|| format!("{} needs a value", flag))?;
+        let number = value.parse::<u64>().map_err(|_| format!("{} needs a number, got `{}`", flag, value))?;
+        match flag.as_str() {
+            "--seed" => seed = number,
+            "--iterations" => iterations = number as u32,
+            "--games" => games = number.max(1),
+            _ => return Err(format!("unknown argument `{}`", flag)),
+        }
+    }
+    return Ok((seed, iterations, games));
+}
+
+fn main() -> ExitCode {
+    let (seed, iterations, games) = match parse_args() {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("tune: {}\n{}", error, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    let (best, _) = search(seed, iterations, games);
+
+    // the search favours candidates that got lucky on the tuning games, so compare on fresh ones
+    let validation_seed = seed + VALIDATION_SEED_OFFSET;
+    report("defaults", &evaluate(&Settings::new(), games, validation_seed));
+    report("best", &evaluate(&best, games, validation_seed));
+
+    println!("settings (changed from the defaults):");
+    let defaults = Settings::new();
+    for parameter in parameters() {
+        let (default, tuned) = ((parameter.get)(&defaults), (parameter.get)(&best));
+        if default != tuned {
+            println!("  {}: {} -> {:.4}", parameter.name, default, tuned);
+        }
+    }
+    return ExitCode::SUCCESS;
+}
@@ -0,0 +1,204 @@
+use oort_api::prelude::*;
+
+use crate::evasion;
+use crate::fleet::FleetPicture;
+use crate::io::{OortIo, ShipIo};
+use crate::point_defense;
+use crate::radar::{Radar, RadarTrack};
+use crate::radio::{DataLink, Packet};
+use crate::settings::Settings;
+use crate::targeting::{TargetSelector, WeaponProfile};
+use crate::utility;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum State {
+    Search,  // nothing to shoot at, sweep the radar
+    Acquire, // a target we only know from radio or have barely seen, get our own radar on it
+    Track,   // a good track, but still out of weapons range
+    Engage,  // in range, the class does its thing
+    Evade,   // something is about to hit us
+    Lost,    // the target stopped showing up, coast on its last track until it is dropped
+}
+
+// A track, predicted to the current tick
+#[derive(Clone, Copy, Debug)]
+pub struct Target {
+    pub id: u32,
+    pub class: Class,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub acceleration: Vec2,
+}
+
+impl Target {
+    pub fn from_track(io: &impl ShipIo, radar_track: &RadarTrack) -> Target {
+        return Target {
+            id: radar_track.id,
+            class: radar_track.class,
+            position: radar_track.position(io),
+            velocity: radar_track.velocity(io),
+            acceleration: radar_track.track.acceleration(),
+        };
+    }
+}
+
+// Everything the state machine shares between classes
+pub struct Core {
+    pub settings: Settings,
+    pub state: State,
+    pub state_since: u32, // tick of the last transition
+    pub target: Option<u32>,
+    pub threats: Vec<u32>, // incoming munitions, most urgent first
+    pub radar: Radar,
+    pub targeting: TargetSelector,
+    pub link: DataLink,
+    pub fleet: FleetPicture,
+}
+
+impl Core {
+    pub fn new(weapon: WeaponProfile, settings: &Settings) -> Core {
+        return Core {
+            settings: *settings,
+            state: State::Search,
+            state_since: 0,
+            target: None,
+            threats: Vec::new(),
+            radar: Radar::new(settings),
+            targeting: TargetSelector::new(weapon, settings),
+            link: DataLink::new(class()),
+            fleet: FleetPicture::new(settings),
+        };
+    }
+
+    pub fn target(&self, io: &impl ShipIo) -> Option<Target> {
+        return self.target.and_then(|id| self.radar.table.get(id)).map(|radar_track| Target::from_track(io, radar_track));
+    }
+}
+
+pub fn next_state(io: &impl ShipIo, core: &Core, evades: bool, engage_range: f64) -> State {
+    if evades && evasion::incoming_missile(io, &core.settings, &core.radar.table).is_some() {
+        return State::Evade;
+    }
+    let Some(radar_track) = core.target.and_then(|id| core.radar.table.get(id)) else {
+        return State::Search;
+    };
+    if radar_track.age(io) > core.settings.track_lost_age {
+        return State::Lost;
+    }
+    if radar_track.last_radar_tick.is_none() || radar_track.track.position_uncertainty() > core.settings.acquire_uncertainty {
+        return State::Acquire;
+    }
+    if (radar_track.position(io) - io.position()).length() > engage_range {
+        return State::Track;
+    }
+    return State::Engage;
+}
+
+pub trait ShipController {
+    fn core(&self) -> &Core;
+    fn core_mut(&mut self) -> &mut Core;
+
+    // -- hooks --
+    fn engage(&mut self, target: &Target); // the class specific attack
+
+    fn engage_range(&self) -> f64 { // m, further out targets are only approached
+        return f64::INFINITY;
+    }
+
+    fn select_target(&mut self, _packets: &[Packet]) -> Option<u32> { // CALLED ONCE PER FRAME
+        let core = self.core_mut();
+        return core.targeting.select(&OortIo, &core.radar.table, &[]);
+    }
+
+    fn search(&mut self) {
+        debug!("Scanning...");
+        accelerate(utility::get_dir_from_heading(heading()) * max_forward_acceleration());
+        // Deactivate boost if no target in sight
+        deactivate_ability(Ability::Boost);
+    }
+
+    fn acquire(&mut self, target: &Target) {
+        self.approach(target);
+    }
+
+    fn approach(&mut self, target: &Target) {
+        self.engage(target);
+    }
+
+    fn lost(&mut self, last_known: &Target) {
+        self.approach(last_known);
+    }
+
+    fn evades(&self) -> bool { // false: incoming munitions are left to point defense
+        return false;
+    }
+
+    fn evade(&mut self, threat: &Target) {
+        if let Some(radar_track) = self.core().radar.table.get(threat.id) {
+            let maneuver = evasion::evade(&mut OortIo, &self.core().settings, radar_track);
+            debug!("Evading {:?}: {:?}", threat.class, maneuver);
+        }
+        activate_ability(Ability::Boost);
+    }
+
+    fn on_transition(&mut self, _from: State, _to: State) {}
+
+    fn after_tick(&mut self, _target: Option<&Target>) {} // runs in every state, e.g. turrets
+
+    fn shares_tracks(&self) -> bool { // false: the ship stays off the data link, it neither hears nor sends
+        return true;
+    }
+
+    // -- state machine --
+    fn tick(&mut self) {
+        // -- sensors --
+        let shares_tracks = self.shares_tracks();
+        let packets = {
+            let core = self.core_mut();
+            let packets = if shares_tracks { core.link.receive() } else { Vec::new() };
+            core.radar.update(&OortIo);
+            core.fleet.update(&OortIo, &packets, &mut core.radar.table);
+            packets
+        };
+        let target = self.select_target(&packets);
+        let core = self.core_mut();
+        core.target = target;
+        core.threats = point_defense::threats(&OortIo, &core.settings, &core.radar.table).iter().map(|radar_track| radar_track.id).collect();
+
+        // -- transitions --
+        let (state, next) = (self.core().state, next_state(&OortIo, self.core(), self.evades(), self.engage_range()));
+        if next != state {
+            self.on_transition(state, next);
+            let core = self.core_mut();
+            core.state = next;
+            core.state_since = current_tick();
+        }
+        debug!("State: {:?}", next);
+
+        // -- behavior --
+        let target = self.core().target(&OortIo);
+        match (next, target) {
+            (State::Evade, _) => {
+                let threat = evasion::incoming_missile(&OortIo, &self.core().settings, &self.core().radar.table).map(|radar_track| Target::from_track(&OortIo, radar_track));
+                if let Some(threat) = threat {
+                    self.evade(&threat);
+                }
+            }
+            (State::Acquire, Some(target)) => self.acquire(&target),
+            (State::Track, Some(target)) => self.approach(&target),
+            (State::Engage, Some(target)) => self.engage(&target),
+            (State::Lost, Some(target)) => self.lost(&target),
+            _ => self.search(),
+        }
+        self.after_tick(target.as_ref());
+
+        // -- radar and radio --
+        let core = self.core_mut();
+        // track the target, glancing around for other contacts every few frames
+        core.radar.steer(&mut OortIo, core.target, core.threats.first().copied());
+        if shares_tracks {
+            core.fleet.broadcast(&OortIo, &core.radar.table, &mut core.link);
+            core.link.flush();
+        }
+    }
+}
@@ -1,87 +1,60 @@
 use oort_api::prelude::*;
-use oort_api::prelude::maths_rs::num::Base;
 
 
 // Cruiser
 use crate::navigation;
-use crate::settings;
+use crate::settings::Settings;
 use crate::ballistics;
 use crate::utility;
-use crate::radar;
+use crate::io::OortIo;
+use crate::point_defense;
+use crate::loadout;
+use crate::controller::{Core, ShipController, Target};
 
 pub struct Cruiser {
-    // Ships
-    last_target_velocity : Vec2,
-    // Radar
-    scans_to_do : i32,
-    best_target : f64,
+    // Weapons
+    weapons : Vec<loadout::Weapon>,
+    flak : loadout::Weapon,
+    // Radar, radio and the state machine
+    core : Core,
 }
 
 impl Cruiser {
-    pub fn new() -> Cruiser {
-        let mut f = Cruiser {
-            last_target_velocity : Vec2::zero(),
-            scans_to_do : 0,
-            best_target : f64::MAX,
-        };
-        radar::reset(&mut f.scans_to_do);
-        f
-    }
-
-    pub fn tick(&mut self) {
-        if self.scans_to_do <= 0 {
-            if let Some(contact) = scan() {
-                if contact.class == Class::Missile { return; }
-
-                radar::track(contact.position, contact.velocity);
-
-                // -- targeting mode --
-                self.cruiser_targeting(contact.position, contact.velocity);
-            } else {
-                // -- activate scanning mode --
-                radar::reset(&mut self.scans_to_do);
-
-                accelerate(utility::get_dir_from_heading(heading()) * max_forward_acceleration());
-            }
-        } else {
-            // -- scanning mode --
-            debug!("Scanning...");
-
-            radar::slice_scan(&mut self.scans_to_do, &mut self.best_target);
-
-            accelerate(utility::get_dir_from_heading(heading()) * max_forward_acceleration());
-
-            // Deactivate boost if no target in sight
-            deactivate_ability(Ability::Boost);
+    pub fn new(settings: &Settings) -> Cruiser {
+        let weapons = loadout::loadout(Class::Cruiser);
+        Cruiser {
+            flak : loadout::main_gun(&weapons).expect("cruisers have a flak turret"),
+            core : Core::new(loadout::weapon_profile(&weapons), settings),
+            weapons,
         }
     }
-    fn cruiser_targeting(&mut self, target: Vec2, target_velocity: Vec2) {
-        let target_acceleration = self.get_target_acceleration(target_velocity);
 
+    fn cruiser_targeting(&mut self, target: Vec2, target_velocity: Vec2, target_acceleration: Vec2, band: (f64, f64)) {
         // calculate angular speed of the target relative to the ship
-        let target_angular_speed = navigation::get_angular_speed(target, target_velocity);
+        let target_angular_speed = navigation::get_angular_speed(&OortIo, target, target_velocity);
 
         // -- acceleration --
-        let acceleration = navigation::calculate_dogfight_acceleration(target, target_angular_speed);
+        // keep the range, the nose stays on the target for the launchers while the flak turret covers every side
+        let acceleration = navigation::hold_band(&OortIo, target, target_velocity, band);
 
         accelerate(acceleration);
 
         // -- turning and aiming --
-        let turret_lead_position = ballistics::lead_from_self(target, target_velocity, target_acceleration, settings::RAILGUN_BULLET_SPEED, settings::ENEMY_SIZE);
+        let intercept = ballistics::intercept(&OortIo, &self.core.settings, target, target_velocity, target_acceleration, self.flak.speed, self.core.settings.enemy_size, self.flak.mount_position(&OortIo));
+        // without a firing solution keep the nose on the target
+        let turret_lead_position = intercept.as_ref().map_or(target, |intercept| intercept.aim_point);
 
         let turning_angle = angle_diff(heading(), (turret_lead_position - position()).angle());
 
-        navigation::turn(turning_angle, target_angular_speed);
-
-        // -- gun logic --
-        fire(1);
-        fire(2);
-        fire(3);
-
-        let turret_angle = (turret_lead_position - position()).angle();
+        navigation::turn(&mut OortIo, turning_angle, target_angular_speed);
 
-        aim(0, turret_angle);
-        fire(0);
+        // -- launcher logic --
+        for launcher in self.weapons.iter().filter(|weapon| !weapon.is_gun()) {
+            if reload_ticks(launcher.slot) == 0 &&
+                (turning_angle.abs() < self.core.settings.missile_fire_angle || (target - position()).length() < self.core.settings.missile_fire_dist) {
+                fire(launcher.slot);
+            }
+        }
 
         // -- debug stuff --
         debug!("total acceleration: {:.2}", acceleration.length());
@@ -89,11 +62,27 @@ impl Cruiser {
         debug!("target dist: {:.2}", (target - position()).length());
 
         draw_line(position(), position() + utility::get_dir_from_heading(heading())*((turret_lead_position - position()).length() + 50.0), 0xff0000); // aim vector
-        draw_polygon(turret_lead_position, settings::ENEMY_SIZE / 2.0, 10, 30.0, 0xf1f100); // lead indicator}
+        draw_polygon(turret_lead_position, self.core.settings.enemy_size / 2.0, 10, 30.0, 0xf1f100); // lead indicator}
+    }
+}
+
+impl ShipController for Cruiser {
+    fn core(&self) -> &Core {
+        return &self.core;
     }
-    fn get_target_acceleration(&mut self, target_velocity: Vec2) -> Vec2 { // CALL ONLY ONCE PER FRAME
-        let acceleration = (target_velocity - self.last_target_velocity) * 60.0; // Calculating target acceleration between this and last frame
-        self.last_target_velocity = target_velocity;
-        return acceleration;
+
+    fn core_mut(&mut self) -> &mut Core {
+        return &mut self.core;
+    }
+
+    fn engage(&mut self, target: &Target) {
+        let band = loadout::engagement_band(&self.core.settings, &self.weapons, target.class);
+        self.cruiser_targeting(target.position, target.velocity, target.acceleration, band);
+    }
+
+    fn after_tick(&mut self, target: Option<&Target>) {
+        // -- point defense --
+        // the turrets defend against the most urgent munitions, the launchers keep working the main target
+        point_defense::turret_logic(&OortIo, &self.core.settings, &self.core.radar.table, &self.core.threats, &self.weapons, target);
     }
 }
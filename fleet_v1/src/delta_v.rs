@@ -0,0 +1,52 @@
+use oort_api::prelude::*;
+
+use crate::controller::Target;
+use crate::io::ShipIo;
+use crate::settings::Settings;
+
+// Munitions get a fixed amount of delta-v: speed up early, coast with enough left to steer, and spend the reserve at the end
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Phase {
+    Boost, // full thrust down the line of sight
+    Coast, // steering only, saving the reserve
+    Terminal, // everything left goes into the intercept
+}
+
+pub fn reserve(io: &impl ShipIo, settings: &Settings) -> f64 { // m/s of delta-v kept back for the terminal phase
+    return io.max_forward_acceleration() * settings.terminal_burn_time;
+}
+
+pub fn time_to_go(io: &impl ShipIo, target: &Target) -> f64 { // s until intercept at the current closing speed
+    let relative_position = target.position - io.position();
+    let closing_speed = -(target.velocity - io.velocity()).dot(relative_position.normalize());
+    if closing_speed <= 0.0 {
+        return f64::INFINITY;
+    }
+    return relative_position.length() / closing_speed;
+}
+
+pub fn planned_closing_speed(io: &impl ShipIo, settings: &Settings, target: &Target) -> f64 { // m/s to boost up to, then coast
+    // Boosting to closing speed u leaves S - (u - Vc) of the spendable delta-v S, and a coast of R / u needs
+    // steering * R / u of it: the fastest u that still affords the coast solves u² - (S + Vc) u + steering * R <= 0.
+    // The distance flown while boosting is small next to the range, so it is left out.
+    let relative_position = target.position - io.position();
+    let closing_speed = -(target.velocity - io.velocity()).dot(relative_position.normalize());
+    let spendable = (io.fuel() - reserve(io, settings)).max(0.0);
+    let steering = settings.coast_steering_acceleration * relative_position.length();
+    let half_sum = (spendable + closing_speed) / 2.0;
+    // a shot too long for the fuel gets the speed that needs the least delta-v overall
+    let discriminant = (half_sum * half_sum - steering).max(0.0);
+    return half_sum + discriminant.sqrt();
+}
+
+pub fn phase(io: &impl ShipIo, settings: &Settings, target: &Target) -> Phase {
+    if time_to_go(io, target) < settings.terminal_time {
+        return Phase::Terminal;
+    }
+    // short shots burn everything but the reserve, long ones stop early and keep fuel for steering on the way
+    let closing_speed = -(target.velocity - io.velocity()).dot((target.position - io.position()).normalize());
+    if io.fuel() > reserve(io, settings) && closing_speed < planned_closing_speed(io, settings, target) {
+        return Phase::Boost;
+    }
+    return Phase::Coast;
+}
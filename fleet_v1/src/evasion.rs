@@ -0,0 +1,48 @@
+use oort_api::prelude::*;
+
+use crate::fuse;
+use crate::io::ShipIo;
+use crate::navigation;
+use crate::point_defense;
+use crate::radar::{RadarTrack, TrackTable};
+use crate::settings::Settings;
+use crate::utility;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Maneuver {
+    Beam,  // keep the missile at 90° to bleed its closing speed
+    Break, // last second hard turn away from its aim point
+}
+
+pub fn incoming_missile<'a>(io: &impl ShipIo, settings: &Settings, table: &'a TrackTable) -> Option<&'a RadarTrack> { // the most urgent missile or torpedo on an intercept course
+    return point_defense::threats(io, settings, table).into_iter()
+        .find(|radar_track| fuse::closest_approach(io, radar_track.position(io), radar_track.velocity(io), radar_track.track.acceleration()).time < settings.evade_warning_time);
+}
+
+pub fn break_time(settings: &Settings, class: Class) -> f64 { // s before impact at which the munition can no longer steer the miss distance back in
+    let stats = class.default_stats();
+    let correction_acceleration = f64::max(stats.max_forward_acceleration, stats.max_lateral_acceleration); // it can point its main engine at us
+    return (2.0 * settings.evade_miss_distance / correction_acceleration).sqrt() + settings.evade_turn_time;
+}
+
+pub fn evade(io: &mut impl ShipIo, settings: &Settings, missile: &RadarTrack) -> Maneuver {
+    let relative_position = io.position() - missile.position(io); // missile to us
+    let relative_velocity = io.velocity() - missile.velocity(io);
+    let time = fuse::closest_approach(io, missile.position(io), missile.velocity(io), missile.track.acceleration()).time;
+
+    let (maneuver, direction) = if time > break_time(settings, missile.class) {
+        // beam: fly across the line of sight, on whichever side we are already moving
+        let normal = utility::normal_vector(relative_position.normalize());
+        (Maneuver::Beam, if normal.dot(io.velocity()) >= 0.0 { normal } else { -normal })
+    } else {
+        // break: accelerate across its path, away from where it would pass us
+        let miss = relative_position + relative_velocity * time;
+        let across = utility::normal_vector(relative_velocity.normalize());
+        (Maneuver::Break, if across.dot(miss) >= 0.0 { across } else { -across })
+    };
+
+    // put the main engine on it, the lateral thrusters are too weak to outrun a missile
+    navigation::turn_to_static(io, angle_diff(io.heading(), direction.angle()));
+    io.accelerate(direction * io.max_forward_acceleration()); // clamped to what the thrusters give while we turn
+    return maneuver;
+}
@@ -0,0 +1,107 @@
+use oort_api::prelude::*;
+
+
+// Fallback for classes without a controller of their own: whatever guns, launchers and radar the ship has
+use crate::navigation;
+use crate::settings::Settings;
+use crate::ballistics;
+use crate::io::OortIo;
+use crate::point_defense;
+use crate::loadout;
+use crate::controller::{Core, ShipController, Target};
+
+pub struct Fallback {
+    // Weapons
+    weapons : Vec<loadout::Weapon>,
+    main_gun : Option<loadout::Weapon>,
+    unknown_slots : Vec<usize>, // slots of a class without a loadout we haven't tried yet
+    probed_slots : Vec<usize>, // fired last tick, a weapon shows up reloading
+    // Radar, radio and the state machine
+    core : Core,
+}
+
+impl Fallback {
+    pub fn new(settings: &Settings) -> Fallback {
+        let weapons = loadout::loadout(class());
+        // nothing tells us what a class we don't know carries, so every slot gets tried once there is something to shoot at
+        let unknown_slots = if weapons.is_empty() { (0..loadout::MAX_WEAPONS).collect() } else { Vec::new() };
+        let profile = if weapons.is_empty() { vec![loadout::unknown_weapon(0)] } else { weapons.clone() }; // target selection needs some idea of our guns up front
+        Fallback {
+            main_gun : loadout::main_gun(&weapons),
+            core : Core::new(loadout::weapon_profile(&profile), settings),
+            weapons,
+            unknown_slots,
+            probed_slots : Vec::new(),
+        }
+    }
+
+    fn detect_weapons(&mut self, target: Option<&Target>) {
+        // a slot that started reloading after the shot holds a weapon, one that didn't is empty
+        for slot in std::mem::take(&mut self.probed_slots) {
+            self.unknown_slots.retain(|unknown| *unknown != slot);
+            if reload_ticks(slot) > 0 {
+                debug!("found a weapon in slot {}", slot);
+                self.weapons.push(loadout::unknown_weapon(slot));
+            }
+        }
+        self.main_gun = loadout::main_gun(&self.weapons);
+
+        let Some(target) = target else { return; };
+        for slot in self.unknown_slots.iter() {
+            aim(*slot, (target.position - position()).angle());
+            fire(*slot);
+            self.probed_slots.push(*slot);
+        }
+    }
+
+    fn fallback_targeting(&mut self, target: Vec2, target_velocity: Vec2, target_acceleration: Vec2) {
+        let target_angular_speed = navigation::get_angular_speed(&OortIo, target, target_velocity);
+
+        // -- acceleration --
+        accelerate(navigation::calculate_dogfight_acceleration(&OortIo, &self.core.settings, target, target_angular_speed));
+
+        // -- turning and aiming --
+        // a fixed gun is aimed with the nose, otherwise the nose just follows the target
+        let intercept = self.main_gun.filter(|gun| !gun.turret).and_then(|gun| {
+            ballistics::intercept(&OortIo, &self.core.settings, target, target_velocity, target_acceleration, gun.speed, self.core.settings.enemy_size, gun.mount_position(&OortIo))
+        });
+        let lead_position = intercept.as_ref().map_or(target, |intercept| intercept.aim_point);
+        let turning_angle = angle_diff(heading(), (lead_position - position()).angle());
+        navigation::turn(&mut OortIo, turning_angle, target_angular_speed);
+
+        // -- gun logic --
+        if let (Some(gun), Some(_)) = (self.main_gun, &intercept) {
+            if turning_angle.abs() <= ballistics::get_angular_target_size(&OortIo, self.core.settings.enemy_size, lead_position) / 2.0 {
+                fire(gun.slot);
+            }
+        }
+        for launcher in self.weapons.iter().filter(|weapon| !weapon.is_gun()) {
+            if reload_ticks(launcher.slot) == 0 &&
+                (turning_angle.abs() < self.core.settings.missile_fire_angle || (target - position()).length() < self.core.settings.missile_fire_dist) {
+                fire(launcher.slot);
+            }
+        }
+    }
+}
+
+impl ShipController for Fallback {
+    fn core(&self) -> &Core {
+        return &self.core;
+    }
+
+    fn core_mut(&mut self) -> &mut Core {
+        return &mut self.core;
+    }
+
+    fn engage(&mut self, target: &Target) {
+        self.fallback_targeting(target.position, target.velocity, target.acceleration);
+    }
+
+    fn after_tick(&mut self, target: Option<&Target>) {
+        // -- weapon detection --
+        self.detect_weapons(target);
+
+        // -- point defense --
+        point_defense::turret_logic(&OortIo, &self.core.settings, &self.core.radar.table, &self.core.threats, &self.weapons, target);
+    }
+}
@@ -2,123 +2,156 @@ use core::f64;
 
 // Fighter
 use oort_api::prelude::*;
-use oort_api::prelude::maths_rs::num::Base;
 
 use crate::navigation;
-use crate::settings;
+use crate::settings::Settings;
 use crate::ballistics;
 use crate::utility;
-use crate::radar;
+use crate::io::OortIo;
+use crate::radio;
+use crate::squadron;
+use crate::evasion;
+use crate::fuse;
+use crate::loadout;
+use crate::controller::{Core, ShipController, Target};
 
 // Crusing mode Stuff
 const C_DIST: f64 = 10000.0;
 
 pub struct Fighter {
-    // Ships
-    last_target_velocity : Vec2,
-    // Radar
-    scans_to_do : i32,
-    best_target : f64,
+    // Weapons
+    weapons : Vec<loadout::Weapon>,
+    gun : loadout::Weapon,
+    // Radar, radio and the state machine
+    core : Core,
+    squadron : squadron::Squadron,
 }
 
 impl Fighter {
-    pub fn new() -> Fighter {
-        let mut f = Fighter {
-            last_target_velocity : Vec2::zero(),
-            scans_to_do : 0,
-            best_target : f64::MAX
-        };
-        radar::reset(&mut f.scans_to_do);
-        f
-    }
-
-    pub fn tick(&mut self) {
-        if self.scans_to_do <= 0 {
-            if let Some(contact) = scan() {
-                if contact.class == Class::Missile { return; }
-
-                radar::track(contact.position, contact.velocity);
-
-                // -- targeting mode --
-                self.fighter_targeting(contact.position, contact.velocity);
-            } else {
-                // -- activate scanning mode --
-                radar::reset(&mut self.scans_to_do);
-
-                accelerate(utility::get_dir_from_heading(heading()) * max_forward_acceleration());
-            }
-        } else {
-            // -- scanning mode --
-            debug!("Scanning...");
-
-            radar::slice_scan(&mut self.scans_to_do, &mut self.best_target);
-
-            accelerate(utility::get_dir_from_heading(heading()) * max_forward_acceleration());
-
-            // Deactivate boost if no target in sight
-            deactivate_ability(Ability::Boost);
+    pub fn new(settings: &Settings) -> Fighter {
+        let weapons = loadout::loadout(Class::Fighter);
+        Fighter {
+            gun : loadout::main_gun(&weapons).expect("fighters have a gun"),
+            core : Core::new(loadout::weapon_profile(&weapons), settings),
+            weapons,
+            squadron : squadron::Squadron::new(settings),
         }
     }
-    pub fn fighter_targeting(&mut self, target: Vec2, target_velocity: Vec2) {
-        let target_acceleration = self.get_target_acceleration(target_velocity);
 
+    pub fn fighter_targeting(&mut self, target: Vec2, target_velocity: Vec2, target_acceleration: Vec2) {
         let relative_position = target - position();
         let relative_velocity = target_velocity - velocity();
 
         let relative_directional_velocity = relative_velocity.dot(relative_position.normalize());
         // main argument (use dogfight mode if the two spaceships are close or closing in)
-        if reload_ticks(0) <= 5 && relative_directional_velocity < settings::BULLET_SPEED && relative_position.length() < C_DIST {
+        if reload_ticks(self.gun.slot) <= 5 && relative_directional_velocity < self.gun.speed && relative_position.length() < C_DIST {
             // If bullets can realistically reach target, enter dogfight mode.
             self.dogfight_mode(target, target_velocity, target_acceleration);
             deactivate_ability(Ability::Boost);
         } else {
-            let acceleration = navigation::calculate_prop_nav_acceleration(target, target_velocity);
+            let acceleration = navigation::calculate_prop_nav_acceleration(&OortIo, &self.core.settings, target, target_velocity);
             debug!("Crusing...");
-            navigation::turn_to_static(angle_diff(heading(), acceleration.angle()));
+            navigation::turn_to_static(&mut OortIo, angle_diff(heading(), acceleration.angle()));
             accelerate(acceleration);
             activate_ability(Ability::Boost)
         }
         // fire missiles if looking in the right direction or close enough the missile can lock on
-        if reload_ticks(1) == 0 &&
-            (angle_diff(heading(), relative_position.angle()).abs() < settings::MISSILE_FIRE_ANGLE || relative_position.length() < settings::MISSILE_FIRE_DIST) {
-            fire(1);
+        for launcher in self.weapons.iter().filter(|weapon| !weapon.is_gun()) {
+            if reload_ticks(launcher.slot) == 0 &&
+                (angle_diff(heading(), relative_position.angle()).abs() < self.core.settings.missile_fire_angle || relative_position.length() < self.core.settings.missile_fire_dist) {
+                fire(launcher.slot);
+            }
         }
     }
     pub fn dogfight_mode(&mut self, target: Vec2, target_velocity: Vec2, target_acceleration: Vec2) {
         // calculate angular speed of the target relative to the ship
-        let target_angular_speed = navigation::get_angular_speed(target, target_velocity);
+        let target_angular_speed = navigation::get_angular_speed(&OortIo, target, target_velocity);
 
         // -- acceleration --
-        let acceleration = navigation::calculate_dogfight_acceleration(target, target_angular_speed);
+        let acceleration = navigation::calculate_dogfight_acceleration(&OortIo, &self.core.settings, target, target_angular_speed);
 
         accelerate(acceleration);
 
         // -- turning and aiming --
-        let lead_position = ballistics::lead_from_self(target, target_velocity, target_acceleration, settings::BULLET_SPEED, settings::ENEMY_SIZE);
+        let intercept = ballistics::intercept(&OortIo, &self.core.settings, target, target_velocity, target_acceleration, self.gun.speed, self.core.settings.enemy_size, self.gun.mount_position(&OortIo));
+        // without a firing solution keep the nose on the target and hold fire
+        let lead_position = intercept.as_ref().map_or(target, |intercept| intercept.aim_point);
         
         let turning_angle = angle_diff(heading(), (lead_position - position()).angle());
         
-        navigation::turn(turning_angle, target_angular_speed);
+        navigation::turn(&mut OortIo, turning_angle, target_angular_speed);
 
         // -- gun logic --
-        let angular_target_size = ballistics::get_angular_target_size(settings::ENEMY_SIZE, lead_position);
+        let angular_target_size = ballistics::get_angular_target_size(&OortIo, self.core.settings.enemy_size, lead_position);
 
-        if turning_angle.abs() <= angular_target_size / 2.0 {
-            fire(0);
+        if intercept.is_some() && turning_angle.abs() <= angular_target_size / 2.0 {
+            fire(self.gun.slot);
         }
 
         // -- debug stuff --
+        if intercept.is_none() {
+            debug!("No firing solution");
+        }
         debug!("total acceleration: {:.2}", acceleration.length());
         debug!("velocity: {:.2}", velocity().length());
         debug!("target dist: {:.2}", (target - position()).length());
 
         draw_line(position(), position() + utility::get_dir_from_heading(heading())*((lead_position - position()).length() + 50.0), 0xff0000); // aim vector
-        draw_polygon(lead_position, settings::ENEMY_SIZE / 2.0, 10, 30.0, 0xf1f100); // lead indicator
+        draw_polygon(lead_position, self.core.settings.enemy_size / 2.0, 10, 30.0, 0xf1f100); // lead indicator
+    }
+}
+
+impl ShipController for Fighter {
+    fn core(&self) -> &Core {
+        return &self.core;
+    }
+
+    fn core_mut(&mut self) -> &mut Core {
+        return &mut self.core;
+    }
+
+    fn engage(&mut self, target: &Target) {
+        self.fighter_targeting(target.position, target.velocity, target.acceleration);
+    }
+
+    fn engage_range(&self) -> f64 { // further out we cruise towards the target
+        return C_DIST;
+    }
+
+    fn select_target(&mut self, packets: &[radio::Packet]) -> Option<u32> {
+        self.squadron.update(&OortIo, packets, &self.core.fleet, &mut self.core.radar.table);
+        self.squadron.lead(&OortIo, &self.core.fleet, &self.core.radar.table, &mut self.core.link);
+
+        // follow the squadron leader, pick our own target if we haven't heard from them
+        return match self.squadron.assigned_target() {
+            Some(id) => {
+                self.core.targeting.assign(id);
+                Some(id)
+            }
+            None => self.core.targeting.select(&OortIo, &self.core.radar.table, &self.squadron.engaged()),
+        };
     }
 
-    pub fn get_target_acceleration(&mut self, target_velocity: Vec2) -> Vec2 { // CALL ONLY ONCE PER FRAME
-        let acceleration = (target_velocity - self.last_target_velocity) * 60.0; // Calculating target acceleration between this and last frame
-        self.last_target_velocity = target_velocity;
-        return acceleration;
+    fn evades(&self) -> bool { // the selector keeps our target, so we go back to it once the missile is past
+        return true;
+    }
+
+    fn evade(&mut self, threat: &Target) {
+        // a missile flying at us is an easy head-on shot, take it while there is still time to break afterwards
+        let Some(radar_track) = self.core.radar.table.get(threat.id) else { return; };
+        let time = fuse::closest_approach(&OortIo, threat.position, threat.velocity, threat.acceleration).time;
+        let intercept = ballistics::intercept(&OortIo, &self.core.settings, threat.position, threat.velocity, threat.acceleration, self.gun.speed, self.core.settings.munition_size, self.gun.mount_position(&OortIo));
+        if let Some(intercept) = intercept.filter(|_| time > evasion::break_time(&self.core.settings, threat.class)) {
+            let turning_angle = angle_diff(heading(), (intercept.aim_point - position()).angle());
+            navigation::turn(&mut OortIo, turning_angle, navigation::get_angular_speed(&OortIo, threat.position, threat.velocity));
+            if turning_angle.abs() <= ballistics::get_angular_target_size(&OortIo, self.core.settings.munition_size, intercept.aim_point) / 2.0 {
+                fire(self.gun.slot);
+            }
+            debug!("Shooting down {:?}", threat.class);
+            return;
+        }
+        let maneuver = evasion::evade(&mut OortIo, &self.core.settings, radar_track);
+        debug!("Evading {:?}: {:?}", threat.class, maneuver);
+        activate_ability(Ability::Boost);
     }
 }
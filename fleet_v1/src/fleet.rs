@@ -0,0 +1,108 @@
+use oort_api::prelude::*;
+
+use crate::io::ShipIo;
+use crate::radar::TrackTable;
+use crate::radio::{DataLink, Packet, RadioMessage, Status, TargetReport};
+use crate::settings::Settings;
+
+// A friendly ship, as last heard from over radio
+#[derive(Clone, Copy, Debug)]
+pub struct Friend {
+    pub id: u32,
+    pub class: Class,
+    pub health: f64,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub tick: u32, // of the last heartbeat
+}
+
+impl Friend {
+    pub fn position(&self, io: &impl ShipIo) -> Vec2 { // predicted to the current tick
+        return self.position + self.velocity * age(io, self.tick);
+    }
+}
+
+// Shares our tracks with the fleet and merges theirs into ours
+pub struct FleetPicture {
+    settings: Settings,
+    friends: Vec<Friend>,
+    report_cursor: usize,
+}
+
+impl FleetPicture {
+    pub fn new(settings: &Settings) -> FleetPicture {
+        return FleetPicture { settings: *settings, friends: Vec::new(), report_cursor: 0 };
+    }
+
+    pub fn update(&mut self, io: &impl ShipIo, packets: &[Packet], table: &mut TrackTable) { // CALL ONCE PER FRAME before using the table
+        // heartbeats first, reports are weighted by where their sender is
+        for packet in packets {
+            if let RadioMessage::Status(status) = packet.message {
+                let friend = Friend { id: packet.sender, class: status.class, health: status.health, position: status.position, velocity: status.velocity, tick: io.current_tick() };
+                match self.friends.iter_mut().find(|friend| friend.id == packet.sender) {
+                    Some(known) => *known = friend,
+                    None => self.friends.push(friend),
+                }
+            }
+        }
+        let friend_timeout = self.settings.friend_timeout;
+        self.friends.retain(|friend| age(io, friend.tick) <= friend_timeout);
+
+        for packet in packets {
+            if let RadioMessage::TargetReport(report) = packet.message {
+                let report_age = age(io, report.tick);
+                if report_age > self.settings.track_drop_time {
+                    continue;
+                }
+                let (position_variance, velocity_variance) = self.report_variance(io, packet.sender, &report);
+                table.ingest_report(io, report.class, report.position + report.velocity * report_age, report.velocity, position_variance, velocity_variance);
+            }
+        }
+    }
+
+    pub fn report_variance(&self, io: &impl ShipIo, sender: u32, report: &TargetReport) -> (f64, f64) { // how much to trust a report, by how far the sender was from the target and how old it is
+        let position_error = match self.friend(sender) {
+            // assume the sender saw it at 0 dB, the worst a detection gets
+            Some(friend) => f64::max((report.position - friend.position(io)).length() * self.settings.radar_noise_mult, self.settings.radar_min_noise),
+            None => self.settings.report_unknown_sender_error,
+        };
+        // and it may have maneuvered since
+        let report_age = age(io, report.tick);
+        let position_error = position_error + 0.5 * self.settings.track_initial_acceleration * report_age * report_age;
+        let velocity_error = position_error * self.settings.radar_velocity_noise_mult + self.settings.track_initial_acceleration * report_age;
+        return (position_error * position_error, velocity_error * velocity_error);
+    }
+
+    pub fn friends(&self) -> impl Iterator<Item = &Friend> {
+        return self.friends.iter();
+    }
+
+    pub fn friend(&self, id: u32) -> Option<&Friend> {
+        return self.friends.iter().find(|friend| friend.id == id);
+    }
+
+    pub fn broadcast(&mut self, io: &impl ShipIo, table: &TrackTable, link: &mut DataLink) { // CALL ONCE PER FRAME before flushing the link
+        if (io.current_tick() + io.id()) % self.settings.heartbeat_interval == 0 {
+            link.send(RadioMessage::Status(Status { class: io.class(), health: io.health(), position: io.position(), velocity: io.velocity() }));
+        }
+
+        // only pass on what our own radar saw recently, relaying other reports would just echo them around the fleet
+        let fresh: Vec<_> = table.tracks()
+            .filter(|radar_track| radar_track.last_radar_tick.is_some_and(|tick| age(io, tick) <= self.settings.report_max_age))
+            .collect();
+        for i in 0..fresh.len().min(self.settings.reports_per_tick) {
+            let radar_track = fresh[(self.report_cursor + i) % fresh.len()];
+            link.send(RadioMessage::TargetReport(TargetReport {
+                class: radar_track.class,
+                position: radar_track.track.position(),
+                velocity: radar_track.track.velocity(),
+                tick: radar_track.track.last_update_tick().unwrap_or(io.current_tick()),
+            }));
+        }
+        self.report_cursor = self.report_cursor.wrapping_add(self.settings.reports_per_tick);
+    }
+}
+
+fn age(io: &impl ShipIo, tick: u32) -> f64 { // s since the tick
+    return io.current_tick().saturating_sub(tick) as f64 * TICK_LENGTH;
+}
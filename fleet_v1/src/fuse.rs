@@ -0,0 +1,48 @@
+use oort_api::prelude::*;
+
+use crate::controller::Target;
+use crate::io::ShipIo;
+
+// Closest approach stuff
+const APPROACH_REFINE_STEPS: i32 = 5; // newton steps that bend the straight-line approach by the target's acceleration
+
+pub struct Approach {
+    pub time: f64, // s from now, 0 once we are already moving apart
+    pub miss_distance: f64, // m between us and the target at that time
+}
+
+fn relative_position(io: &impl ShipIo, position: Vec2, velocity: Vec2, acceleration: Vec2, t: f64) -> Vec2 { // where the object will be relative to us, we keep our velocity
+    return position - io.position() + (velocity - io.velocity()) * t + 0.5 * acceleration * t * t;
+}
+
+// of anything on a known course (targets, incoming munitions), for the fuse as well as for point defense and evasion
+pub fn closest_approach(io: &impl ShipIo, position: Vec2, velocity: Vec2, acceleration: Vec2) -> Approach {
+    let relative_velocity = velocity - io.velocity();
+    let mut time = if relative_velocity.dot(relative_velocity) > 0.0 {
+        (-(position - io.position()).dot(relative_velocity) / relative_velocity.dot(relative_velocity)).max(0.0)
+    } else {
+        0.0
+    };
+    // the distance is smallest where the relative velocity is perpendicular to the relative position
+    for _ in 0..APPROACH_REFINE_STEPS {
+        let offset = relative_position(io, position, velocity, acceleration, time);
+        let closing = relative_velocity + acceleration * time;
+        let slope = closing.dot(closing) + offset.dot(acceleration);
+        if slope <= 0.0 {
+            break;
+        }
+        time = (time - offset.dot(closing) / slope).max(0.0);
+    }
+    return Approach { time, miss_distance: relative_position(io, position, velocity, acceleration, time).length() };
+}
+
+pub fn burst_distance(io: &impl ShipIo, target: &Target, ticks: u32) -> f64 { // distance to the target if we go off that many ticks from now
+    return relative_position(io, target.position, target.velocity, target.acceleration, ticks as f64 * TICK_LENGTH).length();
+}
+
+pub fn detonate_now(io: &impl ShipIo, target: &Target, fuse_range: f64) -> bool {
+    // a warhead goes off after the tick's move, so detonating now bursts one tick out and waiting bursts two ticks out at best.
+    // Going off on the tick closest to the point of closest approach catches passes too fast for a fixed range to see.
+    let now = burst_distance(io, target, 1);
+    return now < fuse_range && now <= burst_distance(io, target, 2);
+}
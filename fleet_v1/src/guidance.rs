@@ -0,0 +1,76 @@
+use oort_api::prelude::*;
+
+use crate::controller::Target;
+use crate::io::ShipIo;
+use crate::utility;
+
+// Guidance laws for munitions, all of them steer across the line of sight and leave the rest of the thrust for closing in
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Law {
+    ProportionalNavigation, // a = N * Vc * λ'
+    AugmentedProportionalNavigation, // PN plus N/2 of the target's acceleration across the line of sight
+    ZeroEffortMiss, // a = N * ZEM / t_go², the miss we would have if nobody steered from here on
+}
+
+pub struct Command {
+    pub acceleration: Vec2, // what we can actually get this tick with the nose where it is
+    pub steering: Vec2, // the part of it across the line of sight, all a coasting munition spends
+    pub heading: f64, // where the nose should point to get all of the commanded acceleration
+}
+
+pub fn lateral_acceleration(io: &impl ShipIo, law: Law, n_factor: f64, target: &Target) -> Vec2 { // commanded acceleration, perpendicular to the line of sight
+    let relative_position = target.position - io.position();
+    let relative_velocity = target.velocity - io.velocity();
+    let line_of_sight = relative_position.normalize();
+    let across = |vector: Vec2| vector - line_of_sight * vector.dot(line_of_sight);
+
+    let closing_speed = -relative_velocity.dot(line_of_sight);
+    if closing_speed <= 0.0 { // moving apart, there is nothing to lead yet: chase until we close
+        return vec2(0.0, 0.0);
+    }
+    let line_of_sight_rate = utility::normal_vector(line_of_sight).dot(relative_velocity) / relative_position.length();
+    let proportional = utility::normal_vector(line_of_sight) * n_factor * closing_speed * line_of_sight_rate;
+    return match law {
+        Law::ProportionalNavigation => proportional,
+        Law::AugmentedProportionalNavigation => proportional + across(target.acceleration) * n_factor / 2.0,
+        Law::ZeroEffortMiss => {
+            let time_to_go = relative_position.length() / closing_speed;
+            let zero_effort_miss = relative_position + relative_velocity * time_to_go + 0.5 * target.acceleration * time_to_go * time_to_go;
+            across(zero_effort_miss) * n_factor / (time_to_go * time_to_go)
+        }
+    };
+}
+
+pub fn command(io: &impl ShipIo, law: Law, n_factor: f64, target: &Target) -> Command {
+    let line_of_sight = (target.position - io.position()).normalize();
+    let lateral = lateral_acceleration(io, law, n_factor, target);
+
+    // With the nose on the commanded acceleration all of the forward thrust is ours: steer first, close in with what is left
+    let max_acceleration = io.max_forward_acceleration();
+    let steering = if lateral.length() > max_acceleration { lateral.normalize() * max_acceleration } else { lateral };
+    let closing = (max_acceleration * max_acceleration - steering.dot(steering)).sqrt();
+    let heading = (steering + line_of_sight * closing).angle();
+
+    // Until the nose gets there we are held to what the thrusters give in the ship frame
+    let steering = scale_into_limits(io, vec2(0.0, 0.0), steering).min(1.0) * steering;
+    let closing = scale_into_limits(io, steering, line_of_sight) * line_of_sight;
+    return Command { acceleration: steering + closing, steering, heading };
+}
+
+fn scale_into_limits(io: &impl ShipIo, start: Vec2, direction: Vec2) -> f64 { // largest k >= 0 with start + k * direction still reachable this tick
+    let start = start.rotate(-io.heading());
+    let direction = direction.rotate(-io.heading());
+    let limits = [
+        (start.x, direction.x, -io.max_backward_acceleration(), io.max_forward_acceleration()),
+        (start.y, direction.y, -io.max_lateral_acceleration(), io.max_lateral_acceleration()),
+    ];
+    let mut scale = f64::INFINITY;
+    for (start, direction, min, max) in limits {
+        if direction > 0.0 {
+            scale = scale.min((max - start) / direction);
+        } else if direction < 0.0 {
+            scale = scale.min((min - start) / direction);
+        }
+    }
+    return scale.max(0.0);
+}
@@ -0,0 +1,59 @@
+use oort_api::prelude::*;
+
+// Everything the navigation, ballistics and radar modules read from or write to the ship
+pub trait ShipIo {
+    // -- sensors --
+    fn current_tick(&self) -> u32;
+    fn id(&self) -> u32;
+    fn class(&self) -> Class;
+    fn health(&self) -> f64;
+    fn fuel(&self) -> f64; // m/s of delta-v left, infinite for ships
+    fn position(&self) -> Vec2;
+    fn velocity(&self) -> Vec2;
+    fn heading(&self) -> f64;
+    fn angular_velocity(&self) -> f64;
+    fn max_forward_acceleration(&self) -> f64;
+    fn max_backward_acceleration(&self) -> f64;
+    fn max_lateral_acceleration(&self) -> f64;
+    fn max_angular_acceleration(&self) -> f64;
+    fn scan(&self) -> Option<ScanResult>;
+    fn radar_heading(&self) -> f64;
+    fn radar_width(&self) -> f64;
+
+    // -- actuators --
+    fn accelerate(&mut self, acceleration: Vec2);
+    fn torque(&mut self, angular_acceleration: f64);
+    fn set_radar_heading(&mut self, heading: f64);
+    fn set_radar_width(&mut self, width: f64);
+    fn set_radar_min_distance(&mut self, dist: f64);
+    fn set_radar_max_distance(&mut self, dist: f64);
+}
+
+// The ship the code is running on, as seen through oort_api
+pub struct OortIo;
+
+impl ShipIo for OortIo {
+    fn current_tick(&self) -> u32 { current_tick() }
+    fn id(&self) -> u32 { id() }
+    fn class(&self) -> Class { class() }
+    fn health(&self) -> f64 { health() }
+    fn fuel(&self) -> f64 { fuel() }
+    fn position(&self) -> Vec2 { position() }
+    fn velocity(&self) -> Vec2 { velocity() }
+    fn heading(&self) -> f64 { heading() }
+    fn angular_velocity(&self) -> f64 { angular_velocity() }
+    fn max_forward_acceleration(&self) -> f64 { max_forward_acceleration() }
+    fn max_backward_acceleration(&self) -> f64 { max_backward_acceleration() }
+    fn max_lateral_acceleration(&self) -> f64 { max_lateral_acceleration() }
+    fn max_angular_acceleration(&self) -> f64 { max_angular_acceleration() }
+    fn scan(&self) -> Option<ScanResult> { scan() }
+    fn radar_heading(&self) -> f64 { radar_heading() }
+    fn radar_width(&self) -> f64 { radar_width() }
+
+    fn accelerate(&mut self, acceleration: Vec2) { accelerate(acceleration) }
+    fn torque(&mut self, angular_acceleration: f64) { torque(angular_acceleration) }
+    fn set_radar_heading(&mut self, heading: f64) { set_radar_heading(heading) }
+    fn set_radar_width(&mut self, width: f64) { set_radar_width(width) }
+    fn set_radar_min_distance(&mut self, dist: f64) { set_radar_min_distance(dist) }
+    fn set_radar_max_distance(&mut self, dist: f64) { set_radar_max_distance(dist) }
+}
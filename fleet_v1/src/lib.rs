@@ -1,15 +1,35 @@
+#![allow(clippy::needless_return, clippy::new_without_default)]
+
 pub mod ship;
+pub mod controller;
 
 pub mod fighter;
 pub mod frigate;
-pub mod missile;
+pub mod munition;
 pub mod cruiser;
+pub mod fallback;
 
 pub mod navigation;
+pub mod guidance;
+pub mod delta_v;
 pub mod ballistics;
 pub mod settings;
+pub mod loadout;
 pub mod utility;
 pub mod radar;
+pub mod tracking;
+pub mod targeting;
+pub mod point_defense;
+pub mod evasion;
+pub mod fuse;
+pub mod io;
+pub mod radio;
+pub mod fleet;
+pub mod squadron;
+
+pub mod sim;
+#[cfg(any(test, feature = "test-support"))]
+pub mod mock;
 
 
 #[allow(unused_imports)]
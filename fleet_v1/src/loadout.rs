@@ -0,0 +1,130 @@
+use oort_api::prelude::*;
+
+use crate::io::ShipIo;
+use crate::settings::Settings;
+use crate::targeting::WeaponProfile;
+
+// Weapons (approximations of the values the game uses)
+pub const MAX_WEAPONS: usize = 4; // slots the game has room for
+
+// Turret stuff
+const HEADING_SEARCH_STEPS: i32 = 180; // per side, when looking for a heading that unmasks every turret
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum WeaponKind {
+    Gun { damage: f64, lifetime: f64, pellets: u32, spread: f64 },
+    Launcher(Class),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Weapon {
+    pub slot: usize, // index for fire(), aim() and reload_ticks()
+    pub kind: WeaponKind,
+    pub speed: f64, // m/s relative to the ship
+    pub reload_ticks: u32,
+    pub offset: Vec2, // mount position relative to the ship (ship frame)
+    pub turret: bool,
+    pub arc: f64, // rad a turret traverses to either side of its mount's outward direction before the hull is in the way
+}
+
+impl Weapon {
+    pub fn is_gun(&self) -> bool {
+        return matches!(self.kind, WeaponKind::Gun { .. });
+    }
+
+    pub fn range(&self) -> f64 { // m a bullet flies before it expires, launched munitions steer themselves
+        return match self.kind {
+            WeaponKind::Gun { lifetime, .. } => self.speed * lifetime,
+            WeaponKind::Launcher(_) => f64::INFINITY,
+        };
+    }
+
+    pub fn damage_per_second(&self) -> f64 {
+        return match self.kind {
+            WeaponKind::Gun { damage, pellets, .. } => damage * pellets as f64 / (self.reload_ticks as f64 * TICK_LENGTH),
+            WeaponKind::Launcher(_) => 0.0,
+        };
+    }
+
+    pub fn mount_position(&self, io: &impl ShipIo) -> Vec2 { // world frame
+        return io.position() + self.offset.rotate(io.heading());
+    }
+
+    pub fn covers(&self, heading: f64, direction: f64) -> bool { // can the turret fire that way with the ship on the heading
+        return angle_diff(heading + self.offset.angle(), direction).abs() <= self.arc;
+    }
+}
+
+fn gun(slot: usize, speed: f64, reload_ticks: u32, damage: f64, lifetime: f64, offset: Vec2, turret: bool) -> Weapon {
+    let arc = if turret { PI } else { 0.0 };
+    return Weapon { slot, kind: WeaponKind::Gun { damage, lifetime, pellets: 1, spread: 0.0 }, speed, reload_ticks, offset, turret, arc };
+}
+
+fn launcher(slot: usize, class: Class, reload_ticks: u32, offset: Vec2) -> Weapon {
+    return Weapon { slot, kind: WeaponKind::Launcher(class), speed: 100.0, reload_ticks, offset, turret: false, arc: 0.0 };
+}
+
+pub fn loadout(class: Class) -> Vec<Weapon> { // every weapon slot of the class
+    return match class {
+        Class::Fighter => vec![
+            gun(0, 1000.0, 4, 20.0, 5.0, vec2(20.0, 0.0), false),
+            launcher(1, Class::Missile, 300, vec2(0.0, 0.0)),
+        ],
+        Class::Frigate => vec![
+            gun(0, 4000.0, 60, 1000.0, 2.5, vec2(40.0, 0.0), false), // railgun
+            // broadside turrets, the hull masks each one's far beam
+            Weapon { arc: 0.75 * PI, ..gun(1, 1000.0, 12, 20.0, 5.0, vec2(0.0, 10.0), true) },
+            Weapon { arc: 0.75 * PI, ..gun(2, 1000.0, 12, 20.0, 5.0, vec2(0.0, -10.0), true) },
+            launcher(3, Class::Missile, 120, vec2(32.0, 0.0)),
+        ],
+        Class::Cruiser => vec![
+            Weapon { // flak
+                slot: 0,
+                kind: WeaponKind::Gun { damage: 20.0, lifetime: 2.0, pellets: 5, spread: 0.03 },
+                speed: 2000.0,
+                reload_ticks: 24,
+                offset: vec2(0.0, 0.0),
+                turret: true,
+                arc: PI,
+            },
+            launcher(1, Class::Missile, 120, vec2(0.0, 30.0)),
+            launcher(2, Class::Missile, 120, vec2(0.0, -30.0)),
+            launcher(3, Class::Torpedo, 180, vec2(100.0, 0.0)),
+        ],
+        _ => vec![],
+    };
+}
+
+pub fn unknown_weapon(slot: usize) -> Weapon { // our guess at a weapon we only know is there: a turret with fighter gun bullets
+    return gun(slot, 1000.0, 4, 20.0, 5.0, vec2(0.0, 0.0), true);
+}
+
+pub fn main_gun(weapons: &[Weapon]) -> Option<Weapon> { // the one the ship aims with its nose, a turret if it has no fixed gun
+    return weapons.iter().filter(|weapon| weapon.is_gun()).min_by_key(|weapon| weapon.turret).copied();
+}
+
+pub fn engagement_band(settings: &Settings, weapons: &[Weapon], target_class: Class) -> (f64, f64) { // (min, max) m to hold from a target
+    // close until every gun reaches, then stay out of the target's guns if they reach less far than ours
+    let gun_ranges = |weapons: &[Weapon]| weapons.iter().filter(|weapon| weapon.is_gun()).map(|weapon| weapon.range()).collect::<Vec<f64>>();
+    let max_range = gun_ranges(weapons).into_iter().fold(f64::INFINITY, f64::min) * settings.engagement_range_share;
+    let threat_range = gun_ranges(&loadout(target_class)).into_iter().fold(0.0, f64::max) * settings.threat_range_share;
+    return (threat_range.min(max_range), max_range);
+}
+
+pub fn unmasked_heading(weapons: &[Weapon], direction: f64, desired: f64) -> f64 { // the heading nearest the desired one at which every turret bears on the direction
+    let bears = |heading: f64| weapons.iter().filter(|weapon| weapon.turret).all(|turret| turret.covers(heading, direction));
+    return (0..=HEADING_SEARCH_STEPS)
+        .flat_map(|i| {
+            let step = PI * i as f64 / HEADING_SEARCH_STEPS as f64;
+            [desired + step, desired - step]
+        })
+        .find(|heading| bears(*heading))
+        .unwrap_or(desired);
+}
+
+pub fn weapon_profile(weapons: &[Weapon]) -> WeaponProfile { // what all guns together do to a target
+    return WeaponProfile {
+        bullet_speed: main_gun(weapons).map_or(1.0, |weapon| weapon.speed),
+        damage_per_second: weapons.iter().map(|weapon| weapon.damage_per_second()).sum::<f64>().max(1e-9),
+    };
+}
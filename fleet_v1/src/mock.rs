@@ -0,0 +1,84 @@
+use oort_api::prelude::*;
+use oort_api::prelude::maths_rs::num::Base;
+
+use crate::io::ShipIo;
+
+// A ship with freely settable sensors that records every actuator command (for tests)
+#[derive(Clone, Debug)]
+pub struct MockIo {
+    pub current_tick: u32,
+    pub id: u32,
+    pub class: Class,
+    pub health: f64,
+    pub fuel: f64,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub heading: f64,
+    pub angular_velocity: f64,
+    pub max_forward_acceleration: f64,
+    pub max_backward_acceleration: f64,
+    pub max_lateral_acceleration: f64,
+    pub max_angular_acceleration: f64,
+    pub contact: Option<ScanResult>,
+    pub radar_heading: f64,
+    pub radar_width: f64,
+    pub radar_min_distance: f64,
+    pub radar_max_distance: f64,
+    // Recorded commands
+    pub accelerations: Vec<Vec2>,
+    pub torques: Vec<f64>,
+}
+
+impl MockIo {
+    pub fn new(class: Class) -> MockIo {
+        let stats = class.default_stats();
+        return MockIo {
+            current_tick: 0,
+            id: 0,
+            class,
+            health: stats.max_health,
+            fuel: f64::INFINITY,
+            position: Vec2::zero(),
+            velocity: Vec2::zero(),
+            heading: 0.0,
+            angular_velocity: 0.0,
+            max_forward_acceleration: stats.max_forward_acceleration,
+            max_backward_acceleration: stats.max_backward_acceleration,
+            max_lateral_acceleration: stats.max_lateral_acceleration,
+            max_angular_acceleration: stats.max_angular_acceleration,
+            contact: None,
+            radar_heading: 0.0,
+            radar_width: TAU,
+            radar_min_distance: 0.0,
+            radar_max_distance: f64::MAX,
+            accelerations: Vec::new(),
+            torques: Vec::new(),
+        };
+    }
+}
+
+impl ShipIo for MockIo {
+    fn current_tick(&self) -> u32 { self.current_tick }
+    fn id(&self) -> u32 { self.id }
+    fn class(&self) -> Class { self.class }
+    fn health(&self) -> f64 { self.health }
+    fn fuel(&self) -> f64 { self.fuel }
+    fn position(&self) -> Vec2 { self.position }
+    fn velocity(&self) -> Vec2 { self.velocity }
+    fn heading(&self) -> f64 { self.heading }
+    fn angular_velocity(&self) -> f64 { self.angular_velocity }
+    fn max_forward_acceleration(&self) -> f64 { self.max_forward_acceleration }
+    fn max_backward_acceleration(&self) -> f64 { self.max_backward_acceleration }
+    fn max_lateral_acceleration(&self) -> f64 { self.max_lateral_acceleration }
+    fn max_angular_acceleration(&self) -> f64 { self.max_angular_acceleration }
+    fn scan(&self) -> Option<ScanResult> { self.contact.clone() }
+    fn radar_heading(&self) -> f64 { self.radar_heading }
+    fn radar_width(&self) -> f64 { self.radar_width }
+
+    fn accelerate(&mut self, acceleration: Vec2) { self.accelerations.push(acceleration) }
+    fn torque(&mut self, angular_acceleration: f64) { self.torques.push(angular_acceleration) }
+    fn set_radar_heading(&mut self, heading: f64) { self.radar_heading = heading }
+    fn set_radar_width(&mut self, width: f64) { self.radar_width = width }
+    fn set_radar_min_distance(&mut self, dist: f64) { self.radar_min_distance = dist }
+    fn set_radar_max_distance(&mut self, dist: f64) { self.radar_max_distance = dist }
+}
@@ -0,0 +1,151 @@
+use core::f64;
+
+// Missiles and torpedoes
+use oort_api::prelude::*;
+
+use crate::navigation;
+use crate::guidance::{self, Law};
+use crate::fuse;
+use crate::delta_v::{self, Phase};
+use crate::settings::Settings;
+use crate::utility;
+use crate::loadout;
+use crate::io::OortIo;
+use crate::radio;
+use crate::radar::RadarTrack;
+use crate::controller::{Core, ShipController, Target};
+
+// Missile stuff
+const MISSILE_FUSE_RANGE: f64 = 30.0; // m, detonate at closest approach if the target passes closer than this
+
+// Torpedo stuff
+const TORPEDO_FUSE_RANGE: f64 = 100.0; // m, the blast radius: anything passing closer still takes part of the warhead
+
+pub struct Munition {
+    // Radar, radio and the state machine
+    core : Core,
+    // Warhead and guidance
+    fuse_range : f64,
+    law : Law,
+    n_factor : f64,
+    prefers : fn(&RadarTrack) -> bool, // what the warhead is meant for, taken over anything else in sight
+    boost : bool, // the class has the boost ability
+    // Launch
+    original_angle : f64,
+    launch_fuel : f64
+}
+
+fn is_any_ship(_radar_track: &RadarTrack) -> bool {
+    return true;
+}
+
+fn is_capital_ship(radar_track: &RadarTrack) -> bool {
+    return radar_track.class == Class::Frigate || radar_track.class == Class::Cruiser;
+}
+
+impl Munition {
+    pub fn new(settings: &Settings, fuse_range: f64, law: Law, n_factor: f64, prefers: fn(&RadarTrack) -> bool) -> Munition {
+        return Munition {
+            core : Core::new(loadout::weapon_profile(&loadout::loadout(class())), settings),
+            fuse_range,
+            law,
+            n_factor,
+            prefers,
+            boost : class() == Class::Missile,
+            original_angle : heading(),
+            launch_fuel : fuel()
+        };
+    }
+
+    pub fn missile(settings: &Settings) -> Munition {
+        return Munition::new(settings, MISSILE_FUSE_RANGE, settings.guidance, settings.n_factor, is_any_ship);
+    }
+
+    pub fn torpedo(settings: &Settings) -> Munition {
+        return Munition::new(settings, TORPEDO_FUSE_RANGE, settings.torpedo_guidance, settings.torpedo_n_factor, is_capital_ship);
+    }
+
+    pub fn munition_targeting(&mut self, target: &Target) {
+        let command = guidance::command(&OortIo, self.law, self.n_factor, target);
+        let phase = delta_v::phase(&OortIo, &self.core.settings, target);
+        debug!("acceleration: {}", command.acceleration.length());
+        debug!("{:?}, fuel: {:.0}", phase, fuel());
+
+        let turning_angle = angle_diff(heading(), command.heading);
+        navigation::turn_to_static(&mut OortIo, turning_angle);
+        if phase == Phase::Coast {
+            accelerate(command.steering);
+        } else {
+            accelerate(command.acceleration);
+        }
+        if !self.boost {
+            return;
+        }
+        if phase != Phase::Coast && turning_angle.abs() < PI / 16.0 { // activate boost as soon as roughly looking at the target
+            activate_ability(Ability::Boost)
+        } else {
+            deactivate_ability(Ability::Boost)
+        }
+    }
+
+    pub fn proximity_fuse(&self, target: &Target) {
+        let approach = fuse::closest_approach(&OortIo, target.position, target.velocity, target.acceleration);
+        debug!("miss distance: {:.1} m in {:.2} s", approach.miss_distance, approach.time);
+        if fuse::detonate_now(&OortIo, target, self.fuse_range) {
+            debug!("Detonating {:.1} m from the target", fuse::burst_distance(&OortIo, target, 1));
+            explode();
+        }
+    }
+}
+
+impl ShipController for Munition {
+    fn core(&self) -> &Core {
+        return &self.core;
+    }
+
+    fn core_mut(&mut self) -> &mut Core {
+        return &mut self.core;
+    }
+
+    fn engage(&mut self, target: &Target) {
+        self.munition_targeting(target);
+        self.proximity_fuse(target);
+    }
+
+    fn select_target(&mut self, _packets: &[radio::Packet]) -> Option<u32> {
+        // the preferred ships over anything else, then the ship closest to where we point, turning is expensive
+        let table = &self.core.radar.table;
+        let prefers = self.prefers;
+        let preferred_in_sight = table.enemies().any(prefers);
+        let eligible = |radar_track: &&RadarTrack| prefers(radar_track) || !preferred_in_sight;
+        if let Some(radar_track) = self.core.target.and_then(|id| table.get(id)).filter(eligible) {
+            return Some(radar_track.id);
+        }
+        return table.enemies()
+            .filter(eligible)
+            .min_by(|a, b| {
+                let off_nose = |point: Vec2| angle_diff(heading(), (point - position()).angle()).abs();
+                off_nose(a.position(&OortIo)).total_cmp(&off_nose(b.position(&OortIo)))
+            })
+            .map(|radar_track| radar_track.id);
+    }
+
+    fn search(&mut self) {
+        debug!("Scanning...");
+
+        // hold the launch heading, and keep most of the fuel for when we find something
+        if fuel() >= self.launch_fuel * (1.0 - self.core.settings.search_fuel_share) {
+            accelerate(utility::get_dir_from_heading(self.original_angle) * max_forward_acceleration() / 2.0);
+        }
+        navigation::turn_to_static(&mut OortIo, angle_diff(heading(), self.original_angle));
+
+        // Deactivate boost if no target in sight
+        if self.boost {
+            deactivate_ability(Ability::Boost);
+        }
+    }
+
+    fn shares_tracks(&self) -> bool { // the radio is for ships, munitions would only crowd it
+        return false;
+    }
+}
@@ -1,78 +1,161 @@
 use oort_api::prelude::*;
 
-use crate::settings;
+use crate::io::ShipIo;
+use crate::settings::Settings;
 use crate::utility;
 
-pub fn turn(angle: f64, target_angular_speed: f64) {
-    // If the angle is so small that one step would be too much reduce acceleration
-    let angular_acceleration = f64::min(2.0*angle.abs()/(TICK_LENGTH*TICK_LENGTH), max_angular_acceleration());
-
-    let time_to_stop = (angular_velocity() + target_angular_speed).abs() / angular_acceleration;
-    let dist_to_stop = (angular_velocity().abs() * time_to_stop - 0.5 * angular_acceleration * time_to_stop * time_to_stop).abs();
-
-    let biased_projected_angle = angle + settings::ANGULAR_SPEED_PREDICTION_FACTOR * target_angular_speed * time_to_stop;
-
-    if dist_to_stop < biased_projected_angle.abs() {
-        // accelerate towards angle
-        torque(angular_acceleration * biased_projected_angle.signum());
-    } else {
-        // brake
-        torque(-angular_acceleration * angular_velocity().signum());
+pub fn turn(io: &mut impl ShipIo, angle: f64, target_angular_speed: f64) { // close the angle and end up turning with the target
+    // Time optimal: close the angle as fast as we can still brake for, then hand over to the target's rate.
+    // Braking counts in whole ticks, k ticks of braking at a cover k(k+1)/2 * a * dt² and start from k * a * dt.
+    let max_angular_acceleration = io.max_angular_acceleration();
+    if max_angular_acceleration <= 0.0 { // nothing to turn with, e.g. a target
+        return;
     }
+    let braking_step = max_angular_acceleration * TICK_LENGTH * TICK_LENGTH;
+    let braking_ticks = ((2.0 * angle.abs() / braking_step + 0.25).sqrt() - 0.5).max(0.0);
+    let closing_speed = angle.signum() * braking_ticks * max_angular_acceleration * TICK_LENGTH;
+
+    // the angular velocity this tick should end with, the sim turns the heading with the new one
+    let desired_angular_velocity = target_angular_speed + closing_speed;
+    let angular_acceleration = (desired_angular_velocity - io.angular_velocity()) / TICK_LENGTH;
+    io.torque(angular_acceleration.clamp(-max_angular_acceleration, max_angular_acceleration));
 }
 
-pub fn turn_to_static(angle: f64) {
-    turn(angle, 0.0);
+pub fn turn_to_static(io: &mut impl ShipIo, angle: f64) {
+    turn(io, angle, 0.0);
 }
 
-pub fn calculate_prop_nav_acceleration(target: Vec2, target_velocity: Vec2) -> Vec2 {
-    let relative_position = target - position();
-    let relative_velocity = target_velocity - velocity();
+pub fn calculate_prop_nav_acceleration(io: &impl ShipIo, settings: &Settings, target: Vec2, target_velocity: Vec2) -> Vec2 {
+    let relative_position = target - io.position();
+    let relative_velocity = target_velocity - io.velocity();
     // V_los = dot(dv, dp) / dp.magnitude
     let relative_directional_velocity = relative_velocity.dot(relative_position.normalize());
     // a_n = N * lambda * V_los
-    let perpendicular_acceleration = settings::N_FACTOR * get_angular_speed(target, target_velocity) * relative_directional_velocity;
+    let perpendicular_acceleration = settings.n_factor * get_angular_speed(io, target, target_velocity) * relative_directional_velocity;
     // a_los = sqrt(a_max²-a_n²)
-    let los_acceleration = if perpendicular_acceleration.abs() > max_forward_acceleration() {
+    let los_acceleration = if perpendicular_acceleration.abs() > io.max_forward_acceleration() {
         // overflow for high perpendicular acceleration
-        return relative_velocity.normalize() * max_forward_acceleration();
+        return relative_velocity.normalize() * io.max_forward_acceleration();
     } else {
-        (max_forward_acceleration()*max_forward_acceleration()-perpendicular_acceleration*perpendicular_acceleration).sqrt()
+        (io.max_forward_acceleration()*io.max_forward_acceleration()-perpendicular_acceleration*perpendicular_acceleration).sqrt()
     };
     // a = los * a_los + n * a_n
     return relative_position.normalize() * los_acceleration - utility::normal_vector(relative_position.normalize()) * perpendicular_acceleration;
 }
 
-pub fn calculate_dogfight_acceleration(target: Vec2, target_angular_speed: f64) -> Vec2 { // Calculate the acceleration in close encounter mode
+pub fn calculate_dogfight_acceleration(io: &impl ShipIo, settings: &Settings, target: Vec2, target_angular_speed: f64) -> Vec2 { // Calculate the acceleration in close encounter mode
 
-    let heading_dir = (target - position()).normalize();
+    let heading_dir = (target - io.position()).normalize();
     let perpendicular_heading_dir = utility::normal_vector(heading_dir) * -target_angular_speed.signum();
-    let relative_forward_acceleration = f64::min((target - position()).length() / settings::MAX_FORWARD_DIST, 1.0);
-    let relative_lateral_acceleration = f64::max(settings::MIN_LATERAL_DIST - (target - position()).length(), 0.0) / settings::MIN_LATERAL_DIST;
+    let relative_forward_acceleration = f64::min((target - io.position()).length() / settings.max_forward_dist, 1.0);
+    let relative_lateral_acceleration = f64::max(settings.min_lateral_dist - (target - io.position()).length(), 0.0) / settings.min_lateral_dist;
     
     let target_dir_angle = (heading_dir * relative_forward_acceleration + perpendicular_heading_dir * relative_lateral_acceleration).angle();
 
-    return get_max_acceleration(target_dir_angle);
+    return get_max_acceleration(io, target_dir_angle);
 }
 
-pub fn get_max_acceleration(direction_angle: f64) -> Vec2 {
-    let relative_angle = direction_angle - heading();
-    let relative_direction = utility::get_dir_from_heading(relative_angle);
+pub fn hold_range(io: &impl ShipIo, target: Vec2, target_velocity: Vec2, range: f64) -> Vec2 { // get to the range and stay there, drifting along with the target
+    let relative_position = target - io.position();
+    let line_of_sight = relative_position.normalize();
+    let relative_velocity = target_velocity - io.velocity();
 
-    let coaxial_acceleration = if relative_direction.x >= 0.0 {
-        max_forward_acceleration()
-    } else {
-        max_backward_acceleration()
-    }.min(max_lateral_acceleration() * (0.5*PI - relative_angle.abs()).tan());
+    // close in (or back off) no faster than we can still brake for: v² = 2 * a * d
+    let range_error = relative_position.length() - range;
+    let braking_acceleration = f64::min(io.max_forward_acceleration(), io.max_backward_acceleration());
+    let desired_closing_speed = range_error.signum() * (2.0 * braking_acceleration * range_error.abs()).sqrt();
+    let closing_speed = -relative_velocity.dot(line_of_sight);
 
-    let lateral_acceleration = relative_angle.abs().tan() * coaxial_acceleration;
+    let radial_acceleration = line_of_sight * (desired_closing_speed - closing_speed) / TICK_LENGTH;
+    let lateral_acceleration = (relative_velocity - line_of_sight * relative_velocity.dot(line_of_sight)) / TICK_LENGTH;
+    return radial_acceleration + lateral_acceleration; // the thrusters clamp it
+}
 
-    return vec2(coaxial_acceleration * relative_direction.x.signum(), lateral_acceleration * relative_direction.y.signum()).rotate(heading());
+pub fn hold_band(io: &impl ShipIo, target: Vec2, target_velocity: Vec2, band: (f64, f64)) -> Vec2 { // close in from beyond the band, open up from inside it, hold anywhere in between
+    let (min_range, max_range) = band;
+    return hold_range(io, target, target_velocity, (target - io.position()).length().clamp(min_range, max_range));
 }
 
-pub fn get_angular_speed(target: Vec2, target_velocity: Vec2) -> f64 { // Get the speed by which the target rotates "around" the ship (in radians)
-    let target_perpendicular_vector = utility::normal_vector((target - position()).normalize());
+pub fn get_max_acceleration(io: &impl ShipIo, direction_angle: f64) -> Vec2 { // the most we can accelerate in that direction, each thruster axis has its own limit
+    let relative_direction = utility::get_dir_from_heading(direction_angle - io.heading());
+    let coaxial_limit = if relative_direction.x >= 0.0 { io.max_forward_acceleration() } else { io.max_backward_acceleration() };
+    let scale = f64::min(
+        if relative_direction.x != 0.0 { coaxial_limit / relative_direction.x.abs() } else { f64::INFINITY },
+        if relative_direction.y != 0.0 { io.max_lateral_acceleration() / relative_direction.y.abs() } else { f64::INFINITY },
+    );
+    return (relative_direction * scale).rotate(io.heading());
+}
+
+pub fn get_angular_speed(io: &impl ShipIo, target: Vec2, target_velocity: Vec2) -> f64 { // Get the speed by which the target rotates "around" the ship (in radians)
+    let target_perpendicular_vector = utility::normal_vector((target - io.position()).normalize());
     let target_relative_speed = target_perpendicular_vector.dot(target_velocity);
-    let self_relative_speed = target_perpendicular_vector.dot(velocity());
-    return (target_relative_speed - self_relative_speed) / (target - position()).length();
-}
\ No newline at end of file
+    let self_relative_speed = target_perpendicular_vector.dot(io.velocity());
+    return (target_relative_speed - self_relative_speed) / (target - io.position()).length();
+}
+
+// -- point to point --
+
+pub fn match_velocity(io: &impl ShipIo, velocity: Vec2) -> Vec2 { // get to the velocity, without overshooting it within the tick
+    let velocity_error = velocity - io.velocity();
+    if velocity_error.length() == 0.0 {
+        return velocity_error;
+    }
+    let max_acceleration = get_max_acceleration(io, velocity_error.angle()).length();
+    return velocity_error.normalize() * f64::min(velocity_error.length() / TICK_LENGTH, max_acceleration);
+}
+
+fn braking_acceleration(io: &impl ShipIo) -> f64 { // what we can count on whichever way we end up facing
+    return io.max_forward_acceleration().min(io.max_backward_acceleration()).min(io.max_lateral_acceleration());
+}
+
+pub fn station_keep(io: &impl ShipIo, point: Vec2, velocity: Vec2) -> Vec2 { // hold a point moving with the velocity, e.g. a slot next to a friend
+    let offset = point - io.position();
+    if offset.length() == 0.0 {
+        return match_velocity(io, velocity);
+    }
+    // come in no faster than we can brake for, and no further than the point within a tick
+    let approach_speed = f64::min((2.0 * braking_acceleration(io) * offset.length()).sqrt(), offset.length() / TICK_LENGTH);
+    return match_velocity(io, velocity + offset.normalize() * approach_speed);
+}
+
+pub fn arrive(io: &impl ShipIo, point: Vec2) -> Vec2 { // go there and stop
+    return station_keep(io, point, vec2(0.0, 0.0));
+}
+
+// Waypoints flown in order, passing through all but the last and stopping on that one
+pub struct Path {
+    pub waypoints: Vec<Vec2>,
+    pub next: usize, // index of the waypoint we are flying to
+    pub radius: f64, // m, a waypoint counts as passed this close
+}
+
+impl Path {
+    pub fn new(waypoints: Vec<Vec2>, radius: f64) -> Path {
+        return Path { waypoints, next: 0, radius };
+    }
+
+    pub fn finished(&self) -> bool {
+        return self.next >= self.waypoints.len();
+    }
+
+    pub fn follow(&mut self, io: &impl ShipIo) -> Vec2 { // keeps holding the last waypoint once there
+        while self.next + 1 < self.waypoints.len() && (self.waypoints[self.next] - io.position()).length() < self.radius {
+            self.next += 1;
+        }
+        let Some(last) = self.waypoints.last() else {
+            return match_velocity(io, vec2(0.0, 0.0));
+        };
+        if self.next + 1 >= self.waypoints.len() {
+            if (*last - io.position()).length() < self.radius {
+                self.next = self.waypoints.len();
+            }
+            return arrive(io, *last);
+        }
+        let waypoint = self.waypoints[self.next];
+        // head for the next waypoint no faster than we can still stop at the end of the path
+        let remaining = (waypoint - io.position()).length()
+            + self.waypoints[self.next..].windows(2).map(|leg| (leg[1] - leg[0]).length()).sum::<f64>();
+        let speed = (2.0 * braking_acceleration(io) * remaining).sqrt();
+        return match_velocity(io, (waypoint - io.position()).normalize() * speed);
+    }
+}
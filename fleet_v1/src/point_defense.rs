@@ -0,0 +1,61 @@
+use oort_api::prelude::*;
+
+use crate::ballistics;
+use crate::controller::Target;
+use crate::fuse;
+use crate::io::ShipIo;
+use crate::loadout::Weapon;
+use crate::radar::{RadarTrack, TrackTable};
+use crate::settings::Settings;
+
+pub fn threats<'a>(io: &impl ShipIo, settings: &Settings, table: &'a TrackTable) -> Vec<&'a RadarTrack> { // incoming missiles and torpedoes, most urgent first
+    let mut threats: Vec<(&RadarTrack, f64)> = table.tracks()
+        .filter(|radar_track| matches!(radar_track.class, Class::Missile | Class::Torpedo))
+        .filter(|radar_track| radar_track.age(io) <= settings.track_lost_age) // a munition we stopped seeing has most likely been shot down
+        .filter(|radar_track| (radar_track.position(io) - io.position()).length() < settings.pd_range)
+        .filter_map(|radar_track| {
+            let approach = fuse::closest_approach(io, radar_track.position(io), radar_track.velocity(io), radar_track.track.acceleration());
+            // missiles still steer, so anything that comes close enough to correct counts as incoming
+            if approach.miss_distance < settings.pd_threat_radius + settings.pd_maneuver_margin * approach.time {
+                return Some((radar_track, approach.time));
+            }
+            return None;
+        })
+        .collect();
+    threats.sort_by(|a, b| a.1.total_cmp(&b.1));
+    return threats.into_iter().map(|(radar_track, _)| radar_track).collect();
+}
+
+// aims and fires a turret at a munition, false without a firing solution the turret bears on
+pub fn engage(io: &impl ShipIo, settings: &Settings, turret: &Weapon, radar_track: &RadarTrack) -> bool {
+    let turret_position = turret.mount_position(io);
+    let intercept = ballistics::intercept(io, settings, radar_track.position(io), radar_track.velocity(io), radar_track.track.acceleration(), turret.speed, settings.munition_size, turret_position);
+    if let Some(intercept) = intercept.filter(|intercept| turret.covers(io.heading(), (intercept.aim_point - turret_position).angle())) {
+        aim(turret.slot, (intercept.aim_point - turret_position).angle());
+        fire(turret.slot);
+        draw_polygon(intercept.aim_point, settings.munition_size, 6, 0.0, 0xff8000); // point defense lead indicator
+        return true;
+    }
+    return false;
+}
+
+// every turret of the ship: split over incoming munitions, they only help with the main target when nothing is incoming
+pub fn turret_logic(io: &impl ShipIo, settings: &Settings, table: &TrackTable, threats: &[u32], weapons: &[Weapon], target: Option<&Target>) {
+    for (i, turret) in weapons.iter().filter(|weapon| weapon.is_gun() && weapon.turret).enumerate() {
+        let turret_position = turret.mount_position(io);
+
+        if let Some(threat) = threats.get(i % threats.len().max(1)).and_then(|id| table.get(*id)) {
+            if engage(io, settings, turret, threat) {
+                continue;
+            }
+        }
+        if let Some(target) = target {
+            let intercept = ballistics::intercept(io, settings, target.position, target.velocity, target.acceleration, turret.speed, settings.enemy_size, turret_position);
+            if let Some(intercept) = intercept.filter(|intercept| turret.covers(io.heading(), (intercept.aim_point - turret_position).angle())) {
+                aim(turret.slot, (intercept.aim_point - turret_position).angle());
+                fire(turret.slot);
+                draw_polygon(intercept.aim_point, settings.enemy_size / 2.0, 10, 30.0, 0x00ff00); // turret lead indicator
+            }
+        }
+    }
+}
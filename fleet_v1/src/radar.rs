@@ -1,39 +1,157 @@
 use oort_api::prelude::*;
 
-use crate::settings;
+use crate::io::ShipIo;
+use crate::settings::Settings;
+use crate::tracking;
+use crate::tracking::Track;
 
-pub fn track(target: Vec2, target_velocity: Vec2) {
-    let target_distance = (target - position()).length();
-    let relative_speed = (target_velocity - velocity()).length();
-    let target_angle = (target - position()).angle();
+pub fn track(io: &mut impl ShipIo, settings: &Settings, target: Vec2, target_velocity: Vec2) {
+    let target_distance = (target - io.position()).length();
+    let relative_speed = (target_velocity - io.velocity()).length();
+    let target_angle = (target - io.position()).angle();
     
     // -- update radar to keep enemy in sight --
-    let target_scan_range = settings::ENEMY_SIZE + settings::TARGET_SCAN_RANGE_MULT * target_distance + settings::TARGET_D_V_MULT * relative_speed;
-    set_radar_heading(target_angle);
-    set_radar_width(target_scan_range / target_distance);
-    set_radar_min_distance(target_distance - target_scan_range / 2.0);
-    set_radar_max_distance(target_distance + target_scan_range / 2.0);
+    let target_scan_range = settings.enemy_size + settings.target_scan_range_mult * target_distance + settings.target_d_v_mult * relative_speed;
+    io.set_radar_heading(target_angle);
+    io.set_radar_width(target_scan_range / target_distance);
+    io.set_radar_min_distance(target_distance - target_scan_range / 2.0);
+    io.set_radar_max_distance(target_distance + target_scan_range / 2.0);
 }
 
-pub fn slice_scan(scans_to_do: &mut i32, best_target: &mut f64) {
-    
-    if let Some(contact) = scan() {
-        if angle_diff(heading(), (contact.position - position()).angle()).abs() < best_target.abs() {
-            *best_target = angle_diff(heading(), (contact.position - position()).angle());
+// -- track while scan --
+
+pub struct RadarTrack {
+    pub id: u32,
+    pub class: Class,
+    pub track: Track,
+    pub last_radar_tick: Option<u32>, // None if we only know it from radio reports
+}
+
+impl RadarTrack {
+    pub fn age(&self, io: &impl ShipIo) -> f64 { // s since the last radar return
+        return io.current_tick().saturating_sub(self.track.last_update_tick().unwrap_or(0)) as f64 * TICK_LENGTH;
+    }
+
+    pub fn position(&self, io: &impl ShipIo) -> Vec2 { // predicted to the current tick
+        return self.track.predicted_position(self.age(io));
+    }
+
+    pub fn velocity(&self, io: &impl ShipIo) -> Vec2 {
+        return self.track.predicted_velocity(self.age(io));
+    }
+
+    pub fn is_enemy_ship(&self) -> bool {
+        return !matches!(self.class, Class::Missile | Class::Torpedo | Class::Asteroid);
+    }
+}
+
+pub struct TrackTable {
+    settings: Settings,
+    tracks: Vec<RadarTrack>,
+    next_id: u32,
+}
+
+impl TrackTable {
+    pub fn new(settings: &Settings) -> TrackTable {
+        return TrackTable { settings: *settings, tracks: Vec::new(), next_id: 1 };
+    }
+
+    pub fn ingest(&mut self, io: &impl ShipIo, contact: &ScanResult) -> u32 { // returns the id of the track the contact was associated to
+        let (position_variance, velocity_variance) = tracking::measurement_variance(io, &self.settings, contact);
+        let id = self.associate(io, contact.class, contact.position, contact.velocity, position_variance, velocity_variance);
+        if let Some(radar_track) = self.tracks.iter_mut().find(|radar_track| radar_track.id == id) {
+            radar_track.last_radar_tick = Some(io.current_tick());
+        }
+        return id;
+    }
+
+    // a measurement from somewhere else (e.g. a friend's radio report), already brought up to the current tick
+    pub fn ingest_report(&mut self, io: &impl ShipIo, class: Class, position: Vec2, velocity: Vec2, position_variance: f64, velocity_variance: f64) -> u32 {
+        return self.associate(io, class, position, velocity, position_variance, velocity_variance);
+    }
+
+    fn associate(&mut self, io: &impl ShipIo, class: Class, position: Vec2, velocity: Vec2, position_variance: f64, velocity_variance: f64) -> u32 {
+        let settings = &self.settings;
+        let closest = self.tracks.iter_mut()
+            .filter(|radar_track| radar_track.class == class)
+            .map(|radar_track| {
+                let age = radar_track.age(io);
+                // gate on the predicted position, widened by how far an unseen maneuver could have moved the target
+                let gate = settings.track_gate_sigmas * (radar_track.track.position_uncertainty() + position_variance.sqrt())
+                    + 0.5 * settings.track_initial_acceleration * age * age + settings.enemy_size;
+                let distance = (radar_track.position(io) - position).length();
+                (radar_track, distance, gate)
+            })
+            .filter(|(_, distance, gate)| distance < gate)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+
+        if let Some((radar_track, _, _)) = closest {
+            radar_track.track.update_measurement(settings, io.current_tick(), position, velocity, position_variance, velocity_variance);
+            return radar_track.id;
         }
+        let mut track = Track::new();
+        track.update_measurement(settings, io.current_tick(), position, velocity, position_variance, velocity_variance);
+        let id = self.next_id;
+        self.next_id += 1;
+        self.tracks.push(RadarTrack { id, class, track, last_radar_tick: None });
+        return id;
+    }
+
+    pub fn drop_stale(&mut self, io: &impl ShipIo) {
+        let drop_time = self.settings.track_drop_time;
+        self.tracks.retain(|radar_track| radar_track.age(io) <= drop_time);
     }
-    *scans_to_do -= 1;
-    if *scans_to_do > 0 {
-        set_radar_heading(radar_heading() + radar_width());
-    } else {
-        set_radar_heading(heading() + *best_target);
-        *best_target = f64::MAX;
+
+    pub fn get(&self, id: u32) -> Option<&RadarTrack> {
+        return self.tracks.iter().find(|radar_track| radar_track.id == id);
+    }
+
+    pub fn tracks(&self) -> impl Iterator<Item = &RadarTrack> {
+        return self.tracks.iter();
+    }
+
+    pub fn enemies(&self) -> impl Iterator<Item = &RadarTrack> { // ships only, no munitions or asteroids
+        return self.tracks.iter().filter(|radar_track| radar_track.is_enemy_ship());
     }
 }
 
-pub fn reset(scans_to_do: &mut i32) {
-    *scans_to_do = settings::SCAN_SECTIONS;
-    set_radar_min_distance(0.0);
-    set_radar_max_distance(f64::MAX);
-    set_radar_width(TAU / settings::SCAN_SECTIONS as f64);
-}
\ No newline at end of file
+pub struct Radar {
+    settings: Settings,
+    pub table: TrackTable,
+    search_heading: f64,
+    ticks_since_search: u32,
+}
+
+impl Radar {
+    pub fn new(settings: &Settings) -> Radar {
+        return Radar { settings: *settings, table: TrackTable::new(settings), search_heading: 0.0, ticks_since_search: 0 };
+    }
+
+    pub fn update(&mut self, io: &impl ShipIo) { // CALL ONCE PER FRAME before using the table
+        if let Some(contact) = io.scan() {
+            self.table.ingest(io, &contact);
+        }
+        self.table.drop_stale(io);
+    }
+
+    pub fn steer(&mut self, io: &mut impl ShipIo, target: Option<u32>, threat: Option<u32>) { // point the beam for the next frame
+        self.ticks_since_search += 1;
+        if self.ticks_since_search < self.settings.tws_search_interval {
+            // with an incoming munition, alternate looks between it and the target
+            let look = if self.ticks_since_search % 2 == 0 { threat.or(target) } else { target.or(threat) };
+            if let Some(radar_track) = look.and_then(|id| self.table.get(id)) {
+                let (position, velocity) = (radar_track.track.predicted_position(radar_track.age(io) + TICK_LENGTH), radar_track.velocity(io));
+                track(io, &self.settings, position, velocity);
+                return;
+            }
+        }
+        // -- search the next sector --
+        self.ticks_since_search = 0;
+        let width = TAU / self.settings.scan_sections as f64;
+        io.set_radar_heading(self.search_heading);
+        io.set_radar_width(width);
+        io.set_radar_min_distance(0.0);
+        io.set_radar_max_distance(f64::MAX);
+        self.search_heading = (self.search_heading + width) % TAU;
+    }
+}
@@ -0,0 +1,268 @@
+use oort_api::prelude::*;
+
+use crate::settings;
+
+// Fleet data-link: typed messages packed into Oort's 32 byte radio payload.
+//
+// layout: [0] tag, [1..5] sender id, [5..31] message body, [31] checksum
+pub const PAYLOAD_SIZE: usize = 32;
+
+const TAG_TARGET_REPORT: u8 = 1;
+const TAG_TARGET_ASSIGNMENT: u8 = 2;
+const TAG_STATUS: u8 = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TargetReport { // a contact the sender is tracking
+    pub class: Class,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub tick: u32, // when it was last seen
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TargetAssignment { // tells one ship which contact to attack
+    pub assignee: u32,
+    pub class: Class,
+    pub position: Vec2,
+    pub velocity: Vec2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Status { // heartbeat, also tells friends where we are
+    pub class: Class,
+    pub health: f64,
+    pub position: Vec2,
+    pub velocity: Vec2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RadioMessage {
+    TargetReport(TargetReport),
+    TargetAssignment(TargetAssignment),
+    Status(Status),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Packet {
+    pub sender: u32,
+    pub message: RadioMessage,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DecodeError {
+    Empty,
+    BadChecksum,
+    UnknownTag(u8),
+    BadClass(u8),
+    NotFinite,
+}
+
+// -- encoding --
+
+struct Writer {
+    bytes: [u8; PAYLOAD_SIZE],
+    cursor: usize,
+}
+
+impl Writer {
+    fn u8(&mut self, value: u8) {
+        self.bytes[self.cursor] = value;
+        self.cursor += 1;
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.bytes[self.cursor..self.cursor + 4].copy_from_slice(&value.to_le_bytes());
+        self.cursor += 4;
+    }
+
+    fn f32(&mut self, value: f64) {
+        self.bytes[self.cursor..self.cursor + 4].copy_from_slice(&(value as f32).to_le_bytes());
+        self.cursor += 4;
+    }
+
+    fn vec2(&mut self, value: Vec2) {
+        self.f32(value.x);
+        self.f32(value.y);
+    }
+}
+
+pub fn encode(packet: &Packet) -> [u8; PAYLOAD_SIZE] {
+    let mut writer = Writer { bytes: [0; PAYLOAD_SIZE], cursor: 0 };
+    let tag = match packet.message {
+        RadioMessage::TargetReport(_) => TAG_TARGET_REPORT,
+        RadioMessage::TargetAssignment(_) => TAG_TARGET_ASSIGNMENT,
+        RadioMessage::Status(_) => TAG_STATUS,
+    };
+    writer.u8(tag);
+    writer.u32(packet.sender);
+
+    match packet.message {
+        RadioMessage::TargetReport(report) => {
+            writer.u8(report.class as u8);
+            writer.vec2(report.position);
+            writer.vec2(report.velocity);
+            writer.u32(report.tick);
+        }
+        RadioMessage::TargetAssignment(assignment) => {
+            writer.u32(assignment.assignee);
+            writer.u8(assignment.class as u8);
+            writer.vec2(assignment.position);
+            writer.vec2(assignment.velocity);
+        }
+        RadioMessage::Status(status) => {
+            writer.u8(status.class as u8);
+            writer.f32(status.health);
+            writer.vec2(status.position);
+            writer.vec2(status.velocity);
+        }
+    }
+
+    writer.bytes[PAYLOAD_SIZE - 1] = checksum(&writer.bytes);
+    return writer.bytes;
+}
+
+// -- decoding --
+
+struct Reader<'a> {
+    bytes: &'a [u8; PAYLOAD_SIZE],
+    cursor: usize,
+}
+
+impl Reader<'_> {
+    fn u8(&mut self) -> u8 {
+        self.cursor += 1;
+        return self.bytes[self.cursor - 1];
+    }
+
+    fn u32(&mut self) -> u32 {
+        self.cursor += 4;
+        return u32::from_le_bytes(self.bytes[self.cursor - 4..self.cursor].try_into().unwrap());
+    }
+
+    fn f32(&mut self) -> Result<f64, DecodeError> {
+        self.cursor += 4;
+        let value = f32::from_le_bytes(self.bytes[self.cursor - 4..self.cursor].try_into().unwrap());
+        if !value.is_finite() {
+            return Err(DecodeError::NotFinite);
+        }
+        return Ok(value as f64);
+    }
+
+    fn vec2(&mut self) -> Result<Vec2, DecodeError> {
+        return Ok(vec2(self.f32()?, self.f32()?));
+    }
+
+    fn class(&mut self) -> Result<Class, DecodeError> {
+        let value = self.u8();
+        if value > Class::Unknown as u8 {
+            return Err(DecodeError::BadClass(value));
+        }
+        return Ok(Class::from_f64(value as f64));
+    }
+}
+
+pub fn decode(bytes: &[u8; PAYLOAD_SIZE]) -> Result<Packet, DecodeError> {
+    if bytes.iter().all(|byte| *byte == 0) {
+        return Err(DecodeError::Empty);
+    }
+    if checksum(bytes) != bytes[PAYLOAD_SIZE - 1] {
+        return Err(DecodeError::BadChecksum);
+    }
+    let mut reader = Reader { bytes, cursor: 0 };
+    let tag = reader.u8();
+    let sender = reader.u32();
+
+    let message = match tag {
+        TAG_TARGET_REPORT => RadioMessage::TargetReport(TargetReport {
+            class: reader.class()?,
+            position: reader.vec2()?,
+            velocity: reader.vec2()?,
+            tick: reader.u32(),
+        }),
+        TAG_TARGET_ASSIGNMENT => RadioMessage::TargetAssignment(TargetAssignment {
+            assignee: reader.u32(),
+            class: reader.class()?,
+            position: reader.vec2()?,
+            velocity: reader.vec2()?,
+        }),
+        TAG_STATUS => RadioMessage::Status(Status {
+            class: reader.class()?,
+            health: reader.f32()?,
+            position: reader.vec2()?,
+            velocity: reader.vec2()?,
+        }),
+        _ => return Err(DecodeError::UnknownTag(tag)),
+    };
+    return Ok(Packet { sender, message });
+}
+
+fn checksum(bytes: &[u8; PAYLOAD_SIZE]) -> u8 { // of everything but the checksum byte itself
+    return bytes[..PAYLOAD_SIZE - 1].iter().fold(0x5a, |sum: u8, byte| sum.rotate_left(1) ^ byte);
+}
+
+// -- data-link --
+
+// Each radio sits on its own channel, so a ship sends and hears as many messages per tick as it has radios.
+// Senders start on a channel picked by their id and the tick: two friends rarely talk over each other,
+// and ships with a single radio, which only hear the first channel, still get every sender now and then.
+pub fn channel(radio: usize) -> usize {
+    return settings::RADIO_BASE_CHANNEL + radio;
+}
+
+pub fn radios(class: Class) -> usize { // fitted by the game
+    return match class {
+        Class::Frigate => 4,
+        Class::Cruiser => settings::RADIO_CHANNELS,
+        _ => 1,
+    };
+}
+
+pub struct DataLink {
+    outbox: Vec<RadioMessage>,
+    radios: usize,
+}
+
+impl DataLink {
+    pub fn new(class: Class) -> DataLink {
+        return DataLink { outbox: Vec::new(), radios: radios(class) };
+    }
+
+    pub fn receive(&mut self) -> Vec<Packet> { // CALL ONCE PER FRAME, everything friends sent last tick
+        let mut packets = Vec::new();
+        for radio in 0..self.radios {
+            select_radio(radio);
+            if let Some(bytes) = receive_bytes() {
+                match decode(&bytes) {
+                    Ok(packet) if packet.sender != id() => packets.push(packet),
+                    Ok(_) => (),
+                    Err(error) => debug!("Dropped radio message on channel {}: {:?}", channel(radio), error),
+                }
+            }
+        }
+        return packets;
+    }
+
+    pub fn send(&mut self, message: RadioMessage) { // queued until flush
+        self.outbox.push(message);
+    }
+
+    pub fn outbox(&self) -> &[RadioMessage] {
+        return &self.outbox;
+    }
+
+    pub fn flush(&mut self) { // CALL ONCE PER FRAME after all sends
+        if self.outbox.len() > self.radios {
+            debug!("Radio overloaded, dropping {} messages", self.outbox.len() - self.radios);
+        }
+        let first_radio = (id() + current_tick()) as usize % self.radios;
+        let mut outbox = self.outbox.drain(..);
+        for i in 0..self.radios {
+            let radio = (first_radio + i) % self.radios;
+            select_radio(radio);
+            set_radio_channel(channel(radio));
+            if let Some(message) = outbox.next() {
+                send_bytes(&encode(&Packet { sender: id(), message }));
+            }
+        }
+    }
+}
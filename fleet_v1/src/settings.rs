@@ -1,27 +1,176 @@
 use oort_api::prelude::*;
 
-// Missile stuff
-pub const N_FACTOR: f64 = 4.0;
-pub const MISSILE_FIRE_DIST: f64 = 6000.0; // missiles are fired if either in range or pointing towards target
-pub const MISSILE_FIRE_ANGLE: f64 = PI * 0.25;
-
-// Prediction stuff
-pub const BULLET_SPEED: f64 = 1000.0; // m/s
-pub const CRUISER_BULLET_SPEED: f64 = 2000.0; // m/s
-pub const RAILGUN_BULLET_SPEED: f64 = 4000.0; // m/s
-pub const PREDICTION_ACCURACY_FACTOR: f64 = 0.1;
-
-// Dogfight movement stuff
-pub const MAX_FORWARD_DIST: f64 = 5000.0;
-pub const MIN_LATERAL_DIST: f64 = 500.0;
-
-// Enemy Stuff
-pub const ENEMY_SIZE: f64 = 25.0;
-
-// Radar stuff
-pub const TARGET_SCAN_RANGE_MULT: f64 = 0.02;
-pub const TARGET_D_V_MULT: f64 = 0.04;
-pub const SCAN_SECTIONS: i32 = 10;
-
-// Basic navigation stuff
-pub const ANGULAR_SPEED_PREDICTION_FACTOR: f64 = 0.2;
+use crate::guidance::Law;
+
+// Radio stuff (every ship has to agree on these, so they are not tunable)
+pub const RADIO_BASE_CHANNEL: usize = 1; // the data-link uses this and the following channels
+pub const RADIO_CHANNELS: usize = 8; // one per radio of the best fitted ship
+
+// Everything we tune. Ships get their copy at construction and pass it to the modules that need it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Settings {
+    // Missile stuff
+    pub n_factor: f64,
+    pub missile_fire_dist: f64, // missiles are fired if either in range or pointing towards target
+    pub missile_fire_angle: f64,
+    pub guidance: Law, // how missiles steer
+    pub terminal_time: f64, // s to go at which munitions spend their reserve
+    pub terminal_burn_time: f64, // s of full thrust munitions keep in reserve for the terminal phase
+    pub coast_steering_acceleration: f64, // m/s² munitions expect to spend steering while they coast, long shots boost less to afford it
+    pub search_fuel_share: f64, // of its launch fuel a munition burns flying out before it has a target
+
+    // Torpedo stuff
+    pub torpedo_guidance: Law, // slow and heavy, torpedoes have to lead their targets from further out
+    pub torpedo_n_factor: f64,
+
+    // Prediction stuff
+    pub prediction_accuracy_factor: f64,
+    pub max_intercept_time: f64, // s, longer shots are not worth taking
+
+    // Dogfight movement stuff
+    pub max_forward_dist: f64,
+    pub min_lateral_dist: f64,
+
+    // Capital ship stuff
+    pub engagement_range_share: f64, // frigates and cruisers close in until their shortest gun reaches the target with this share of its range
+    pub threat_range_share: f64, // and open up to this share of the target's gun range, as far as the band allows
+
+    // Enemy Stuff
+    pub enemy_size: f64,
+
+    // Radar stuff
+    pub target_scan_range_mult: f64,
+    pub target_d_v_mult: f64,
+    pub scan_sections: i32,
+    pub radar_noise_mult: f64, // position error per meter of range at 0 dB SNR
+    pub radar_velocity_noise_mult: f64, // velocity error (m/s) per meter of position error
+    pub radar_min_noise: f64, // m
+
+    // Tracking stuff
+    pub track_jerk_noise: f64, // how fast targets can change their acceleration (m²/s⁵)
+    pub track_initial_acceleration: f64, // m/s², prior uncertainty of a new track
+    pub track_gate_sigmas: f64, // measurements further off start a new track
+    pub track_timeout: f64, // s without updates before a track is started over
+    pub track_drop_time: f64, // s without a radar return before a track is forgotten
+    pub tws_search_interval: u32, // while tracking, every n-th tick the beam searches for other contacts
+    pub track_lost_age: f64, // s without updates before the target counts as lost
+    pub acquire_uncertainty: f64, // m, a target track has to be this good before we engage it
+
+    // Targeting stuff
+    pub target_range_scale: f64, // m, a target this far away scores half of one next to us
+    pub target_closing_scale: f64, // m/s, a target closing this fast scores double
+    pub target_engaged_factor: f64, // score multiplier for targets a friend is already shooting at
+    pub target_switch_ratio: f64, // a new target has to score this much better to take over
+
+    // Point defense stuff
+    pub pd_range: f64, // m, munitions further out are left alone
+    pub pd_threat_radius: f64, // m, munitions passing closer than this are incoming
+    pub pd_maneuver_margin: f64, // m of extra miss distance per second until closest approach, missiles can still correct
+    pub munition_size: f64,
+
+    // Evasion stuff
+    pub evade_warning_time: f64, // s before impact at which fighters stop attacking and start evading
+    pub evade_miss_distance: f64, // m, how far off we want the missile to pass
+    pub evade_turn_time: f64, // s a fighter needs to swing its nose into the break
+
+    // Radio stuff
+    pub heartbeat_interval: u32, // ticks between status messages
+    pub friend_timeout: f64, // s without a heartbeat before a friend counts as lost
+    pub reports_per_tick: usize, // target reports a ship sends per tick, leaves room for its friends on the channels
+    pub report_max_age: f64, // s, older radar returns are not worth reporting
+    pub report_unknown_sender_error: f64, // m, assumed error of reports from ships we haven't heard a heartbeat from
+
+    // Squadron stuff
+    pub assignment_timeout: f64, // s without hearing our assignment again before we pick targets on our own
+    pub assignments_per_tick: usize, // the leader cycles through the squadron this many fighters at a time
+}
+
+impl Settings {
+    pub fn new() -> Settings { // the hand-tuned values every class starts from
+        return Settings {
+            n_factor: 4.0,
+            missile_fire_dist: 6000.0,
+            missile_fire_angle: PI * 0.25,
+            guidance: Law::AugmentedProportionalNavigation,
+            terminal_time: 3.0,
+            terminal_burn_time: 2.0,
+            coast_steering_acceleration: 30.0,
+            search_fuel_share: 0.25,
+
+            torpedo_guidance: Law::ZeroEffortMiss,
+            torpedo_n_factor: 3.0,
+
+            prediction_accuracy_factor: 0.1,
+            max_intercept_time: 10.0,
+
+            max_forward_dist: 5000.0,
+            min_lateral_dist: 500.0,
+
+            engagement_range_share: 0.7,
+            threat_range_share: 1.1,
+
+            enemy_size: 25.0,
+
+            target_scan_range_mult: 0.02,
+            target_d_v_mult: 0.04,
+            scan_sections: 10,
+            radar_noise_mult: 0.01,
+            radar_velocity_noise_mult: 0.1,
+            radar_min_noise: 0.5,
+
+            track_jerk_noise: 1000.0,
+            track_initial_acceleration: 60.0,
+            track_gate_sigmas: 6.0,
+            track_timeout: 1.0,
+            track_drop_time: 2.0,
+            tws_search_interval: 4,
+            track_lost_age: 0.5,
+            acquire_uncertainty: 50.0,
+
+            target_range_scale: 3000.0,
+            target_closing_scale: 200.0,
+            target_engaged_factor: 0.5,
+            target_switch_ratio: 1.5,
+
+            pd_range: 3000.0,
+            pd_threat_radius: 200.0,
+            pd_maneuver_margin: 50.0,
+            munition_size: 6.0,
+
+            evade_warning_time: 5.0,
+            evade_miss_distance: 100.0,
+            evade_turn_time: 0.5,
+
+            heartbeat_interval: 10,
+            friend_timeout: 2.0,
+            reports_per_tick: 2,
+            report_max_age: 0.5,
+            report_unknown_sender_error: 100.0,
+
+            assignment_timeout: 1.0,
+            assignments_per_tick: 3,
+        };
+    }
+}
+
+// Settings per ship class: the defaults, with whole-set overrides for the classes that fly differently
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClassSettings {
+    pub default: Settings,
+    pub overrides: Vec<(Class, Settings)>,
+}
+
+impl ClassSettings {
+    pub fn new() -> ClassSettings {
+        return ClassSettings { default: Settings::new(), overrides: Vec::new() };
+    }
+
+    pub fn get(&self, class: Class) -> Settings {
+        return self.overrides.iter().find(|(overridden, _)| *overridden == class).map_or(self.default, |(_, settings)| *settings);
+    }
+
+    pub fn set(&mut self, class: Class, settings: Settings) {
+        self.overrides.retain(|(overridden, _)| *overridden != class);
+        self.overrides.push((class, settings));
+    }
+}
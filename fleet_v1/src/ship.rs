@@ -1,35 +1,70 @@
 use oort_api::prelude::*;
 
 use crate::fighter::*;
-use crate::missile::*;
+use crate::munition::*;
 use crate::frigate::*;
 use crate::cruiser::*;
+use crate::fallback::*;
+use crate::controller::ShipController;
+use crate::settings::{ClassSettings, Settings};
 
-pub enum Ship {
-    Fighter(Fighter),
-    Missile(Missile),
-    Frigate(Frigate),
-    Cruiser(Cruiser)
+pub type Constructor = fn(&Settings) -> Box<dyn ShipController>;
+
+// Which controller flies which class, classes without one get the fallback
+pub struct Registry {
+    pub controllers: Vec<(Class, Constructor)>,
+}
+
+impl Registry {
+    pub fn new() -> Registry { // the classes we have a controller for
+        let mut registry = Registry { controllers: Vec::new() };
+        registry.register(Class::Fighter, |settings| Box::new(Fighter::new(settings)));
+        registry.register(Class::Missile, |settings| Box::new(Munition::missile(settings)));
+        registry.register(Class::Frigate, |settings| Box::new(Frigate::new(settings)));
+        registry.register(Class::Cruiser, |settings| Box::new(Cruiser::new(settings)));
+        registry.register(Class::Torpedo, |settings| Box::new(Munition::torpedo(settings)));
+        return registry;
+    }
+
+    pub fn register(&mut self, class: Class, constructor: Constructor) {
+        self.controllers.retain(|(registered, _)| *registered != class);
+        self.controllers.push((class, constructor));
+    }
+
+    pub fn get(&self, class: Class) -> Option<Constructor> {
+        return self.controllers.iter().find(|(registered, _)| *registered == class).map(|(_, constructor)| *constructor);
+    }
+}
+
+pub struct Ship {
+    controller: Box<dyn ShipController>,
+    warning: Option<String>, // repeated every tick, debug output only lasts one
 }
 
 impl Ship {
     pub fn new() -> Ship {
-        match class() {
-            Class::Fighter => { Ship::Fighter(Fighter::new()) }
-            Class::Missile => { Ship::Missile(Missile::new()) }
-            Class::Frigate => { Ship::Fighter(Fighter::new()) }
-            Class::Cruiser => { Ship::Cruiser(Cruiser::new()) }
-            Class::Torpedo => { Ship::Missile(Missile::new()) } // Torpedo is a missile with a different name (for now)
-            _ => { panic!("ship class is unknown") }
-        }
+        return Ship::with_settings(&ClassSettings::new());
+    }
+
+    pub fn with_settings(settings: &ClassSettings) -> Ship { // for trying out tuning variants
+        return Ship::with_registry(&Registry::new(), settings);
+    }
+
+    pub fn with_registry(registry: &Registry, settings: &ClassSettings) -> Ship {
+        let settings = &settings.get(class());
+        return match registry.get(class()) {
+            Some(constructor) => Ship { controller: constructor(settings), warning: None },
+            None => Ship {
+                controller: Box::new(Fallback::new(settings)),
+                warning: Some(format!("warning: no controller for {:?}, flying the fallback", class())),
+            },
+        };
     }
 
     pub fn tick(&mut self) {
-        match self {
-            Ship::Fighter(implementation) => { implementation.tick(); }
-            Ship::Missile(implementation) => { implementation.tick(); }
-            Ship::Frigate(implementation) => { implementation.tick(); }
-            Ship::Cruiser(implementation) => { implementation.tick(); }
+        if let Some(warning) = &self.warning {
+            debug!("{}", warning);
         }
+        self.controller.tick();
     }
-}
\ No newline at end of file
+}
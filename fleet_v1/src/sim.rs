@@ -0,0 +1,623 @@
+// Headless simulator
+// Drives the ship controllers outside of the Oort web client by writing each ship's sensors into
+// the oort_api system state, running its tick and reading the actuators back out.
+use std::sync::Mutex;
+
+use oort_api::prelude::*;
+use oort_api::prelude::maths_rs::num::Base;
+use oort_api::prelude::radio_internal::{radio_indices, MAX_RADIOS};
+use oort_api::prelude::oorandom::Rand64;
+use oort_api::sys::{read_system_state, read_system_state_u64, write_system_state, write_system_state_u64};
+use oort_api::{ActiveAbilities, SystemState};
+
+use crate::loadout::{self, WeaponKind};
+use crate::radio;
+use crate::settings::ClassSettings;
+use crate::ship::Ship;
+use crate::utility;
+
+// The oort_api system state is a single global, so only one ship may run its tick at a time
+static SYSTEM_STATE_LOCK: Mutex<()> = Mutex::new(());
+
+// Radar stuff
+const RADAR_POWER: f64 = 2.5e8; // a fighter sees a fighter at ~20km with a tenth of a circle
+const RADAR_NOISE_MULT: f64 = 0.01;
+
+// Ability stuff
+const BOOST_ACCELERATION: f64 = 100.0; // m/s²
+const BOOST_TICKS: u32 = 120;
+const BOOST_RELOAD_TICKS: u32 = 600;
+const SHIELD_TICKS: u32 = 60;
+const SHIELD_RELOAD_TICKS: u32 = 300;
+
+pub fn radius(class: Class) -> f64 {
+    match class {
+        Class::Fighter => 10.0,
+        Class::Frigate => 30.0,
+        Class::Cruiser => 60.0,
+        Class::Asteroid => 50.0,
+        Class::Target => 10.0,
+        Class::Missile => 3.0,
+        Class::Torpedo => 5.0,
+        Class::Unknown => 10.0,
+    }
+}
+
+fn radar_cross_section(class: Class) -> f64 {
+    match class {
+        Class::Fighter | Class::Target => 1.0,
+        Class::Frigate => 4.0,
+        Class::Cruiser => 8.0,
+        Class::Asteroid => 4.0,
+        Class::Missile => 0.2,
+        Class::Torpedo => 0.5,
+        Class::Unknown => 1.0,
+    }
+}
+
+fn initial_fuel(class: Class) -> f64 {
+    match class {
+        Class::Missile => 2000.0, // m/s of delta-v
+        Class::Torpedo => 5000.0,
+        _ => f64::INFINITY,
+    }
+}
+
+fn warhead(class: Class) -> Option<(f64, f64)> { // (damage, blast radius)
+    match class {
+        Class::Missile => Some((150.0, 50.0)),
+        Class::Torpedo => Some((3000.0, 100.0)),
+        _ => None,
+    }
+}
+
+fn is_munition(class: Class) -> bool {
+    class == Class::Missile || class == Class::Torpedo
+}
+
+fn has_controller(class: Class) -> bool {
+    class != Class::Asteroid && class != Class::Target && class != Class::Unknown
+}
+
+pub enum Pilot {
+    Fleet(Option<Box<Ship>>), // the fleet_v1 code, created on the ship's first tick
+    Scripted(Box<dyn FnMut()>), // any closure using the oort_api functions
+    Idle,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Contact {
+    pub class: Class,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub rssi: f64,
+    pub snr: f64,
+}
+
+#[derive(Clone, Copy)]
+struct Radar {
+    heading: f64,
+    width: f64,
+    min_distance: f64,
+    max_distance: f64,
+    contact: Option<Contact>,
+}
+
+#[derive(Clone, Copy, Default)]
+struct Radio {
+    channel: f64,
+    received: Option<Message>,
+    sent: Option<Message>,
+}
+
+pub struct SimShip {
+    pub id: u32,
+    pub class: Class,
+    pub team: usize,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub heading: f64,
+    pub angular_velocity: f64,
+    pub health: f64,
+    pub fuel: f64,
+    pub damage_taken: f64,
+    pub hits: [u32; 4], // bullets per weapon slot that found an enemy
+    pub death_tick: Option<u32>,
+    pub weapons: Vec<loadout::Weapon>, // the class's loadout unless a test fits something else
+    pilot: Pilot,
+    // Actuators
+    acceleration: Vec2, // ship frame
+    torque: f64,
+    aim: [f64; 4],
+    fire: [bool; 4],
+    explode: bool,
+    abilities: u64,
+    // Internal state
+    reload: [u32; 4],
+    boost_ticks: u32,
+    boost_reload: u32,
+    shield_ticks: u32,
+    shield_reload: u32,
+    radar: Radar,
+    radios: [Radio; MAX_RADIOS],
+    selected_radio: f64,
+}
+
+impl SimShip {
+    pub fn alive(&self) -> bool {
+        return self.death_tick.is_none();
+    }
+
+    fn max_accelerations(&self) -> (f64, f64, f64, f64) { // (forward, backward, lateral, angular)
+        let stats = self.class.default_stats();
+        return (stats.max_forward_acceleration, stats.max_backward_acceleration, stats.max_lateral_acceleration, stats.max_angular_acceleration);
+    }
+
+    fn load_system_state(&self, tick: u32, seed: u64) {
+        let (forward, backward, lateral, angular) = self.max_accelerations();
+        write_system_state(SystemState::Class, self.class as u32 as f64);
+        write_system_state(SystemState::Seed, seed as f64);
+        write_system_state(SystemState::Id, self.id as f64);
+        write_system_state(SystemState::CurrentTick, tick as f64);
+        write_system_state(SystemState::PositionX, self.position.x);
+        write_system_state(SystemState::PositionY, self.position.y);
+        write_system_state(SystemState::VelocityX, self.velocity.x);
+        write_system_state(SystemState::VelocityY, self.velocity.y);
+        write_system_state(SystemState::Heading, self.heading);
+        write_system_state(SystemState::AngularVelocity, self.angular_velocity);
+        write_system_state(SystemState::Health, self.health);
+        write_system_state(SystemState::Fuel, self.fuel);
+        write_system_state(SystemState::MaxForwardAcceleration, forward);
+        write_system_state(SystemState::MaxBackwardAcceleration, backward);
+        write_system_state(SystemState::MaxLateralAcceleration, lateral);
+        write_system_state(SystemState::MaxAngularAcceleration, angular);
+
+        // -- actuators start every tick cleared --
+        write_system_state(SystemState::AccelerateX, 0.0);
+        write_system_state(SystemState::AccelerateY, 0.0);
+        write_system_state(SystemState::Torque, 0.0);
+        write_system_state(SystemState::Explode, 0.0);
+        let fire = [SystemState::Fire0, SystemState::Fire1, SystemState::Fire2, SystemState::Fire3];
+        let aim = [SystemState::Aim0, SystemState::Aim1, SystemState::Aim2, SystemState::Aim3];
+        let reload = [SystemState::ReloadTicks0, SystemState::ReloadTicks1, SystemState::ReloadTicks2, SystemState::ReloadTicks3];
+        for i in 0..4 {
+            write_system_state(fire[i], 0.0);
+            write_system_state(aim[i], self.aim[i]);
+            write_system_state(reload[i], self.reload[i] as f64);
+        }
+        write_system_state_u64(SystemState::ActivateAbility, self.abilities);
+
+        // -- radar --
+        write_system_state(SystemState::RadarHeading, self.radar.heading);
+        write_system_state(SystemState::RadarWidth, self.radar.width);
+        write_system_state(SystemState::RadarMinDistance, self.radar.min_distance);
+        write_system_state(SystemState::RadarMaxDistance, self.radar.max_distance);
+        write_system_state(SystemState::RadarEcmMode, 0.0);
+        if let Some(contact) = self.radar.contact {
+            write_system_state(SystemState::RadarContactFound, 1.0);
+            write_system_state(SystemState::RadarContactClass, contact.class as u32 as f64);
+            write_system_state(SystemState::RadarContactPositionX, contact.position.x);
+            write_system_state(SystemState::RadarContactPositionY, contact.position.y);
+            write_system_state(SystemState::RadarContactVelocityX, contact.velocity.x);
+            write_system_state(SystemState::RadarContactVelocityY, contact.velocity.y);
+            write_system_state(SystemState::RadarContactRssi, contact.rssi);
+            write_system_state(SystemState::RadarContactSnr, contact.snr);
+        } else {
+            write_system_state(SystemState::RadarContactFound, 0.0);
+        }
+
+        // -- radio --
+        write_system_state(SystemState::SelectedRadio, self.selected_radio);
+        for (i, radio) in self.radios.iter().enumerate() {
+            let indices = radio_indices(i);
+            write_system_state(indices.channel, radio.channel);
+            write_system_state(indices.send, 0.0);
+            let data = radio.received.unwrap_or([0.0; 4]);
+            write_system_state(indices.receive, if radio.received.is_some() { 1.0 } else { 0.0 });
+            for (index, value) in indices.data.iter().zip(data) {
+                write_system_state(*index, value);
+            }
+        }
+    }
+
+    fn store_system_state(&mut self) {
+        let (forward, backward, lateral, angular) = self.max_accelerations();
+        self.acceleration = vec2(
+            read_system_state(SystemState::AccelerateX).clamp(-backward, forward),
+            read_system_state(SystemState::AccelerateY).clamp(-lateral, lateral),
+        );
+        self.torque = read_system_state(SystemState::Torque).clamp(-angular, angular);
+        self.explode = read_system_state(SystemState::Explode) != 0.0;
+        let fire = [SystemState::Fire0, SystemState::Fire1, SystemState::Fire2, SystemState::Fire3];
+        let aim = [SystemState::Aim0, SystemState::Aim1, SystemState::Aim2, SystemState::Aim3];
+        for i in 0..4 {
+            self.fire[i] = read_system_state(fire[i]) != 0.0;
+            self.aim[i] = read_system_state(aim[i]);
+        }
+        self.abilities = read_system_state_u64(SystemState::ActivateAbility);
+
+        self.radar.heading = read_system_state(SystemState::RadarHeading);
+        self.radar.width = read_system_state(SystemState::RadarWidth).clamp(TAU / 3600.0, TAU);
+        self.radar.min_distance = read_system_state(SystemState::RadarMinDistance);
+        self.radar.max_distance = read_system_state(SystemState::RadarMaxDistance);
+
+        self.selected_radio = read_system_state(SystemState::SelectedRadio);
+        for (i, radio) in self.radios.iter_mut().enumerate() {
+            let indices = radio_indices(i);
+            radio.channel = read_system_state(indices.channel);
+            radio.sent = if read_system_state(indices.send) != 0.0 {
+                Some([
+                    read_system_state(indices.data[0]),
+                    read_system_state(indices.data[1]),
+                    read_system_state(indices.data[2]),
+                    read_system_state(indices.data[3]),
+                ])
+            } else {
+                None
+            };
+        }
+    }
+
+    fn run_pilot(&mut self, tick: u32, seed: u64, settings: &ClassSettings) {
+        let _guard = SYSTEM_STATE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        self.load_system_state(tick, seed);
+        oort_api::dbg::reset();
+        match &mut self.pilot {
+            Pilot::Fleet(ship) => { ship.get_or_insert_with(|| Box::new(Ship::with_settings(settings))).tick(); }
+            Pilot::Scripted(script) => { script(); }
+            Pilot::Idle => { return; }
+        }
+        self.store_system_state();
+    }
+}
+
+struct Bullet {
+    team: usize,
+    shooter: (u32, usize), // ship id and weapon slot
+    position: Vec2,
+    velocity: Vec2,
+    damage: f64,
+    expire_tick: u32,
+}
+
+pub struct Simulation {
+    pub ships: Vec<SimShip>,
+    bullets: Vec<Bullet>,
+    tick: u32,
+    seed: u64,
+    rng: Rand64,
+    next_id: u32,
+    radar_noise: f64,
+    team_settings: Vec<(usize, ClassSettings)>, // teams without an entry fly the defaults
+    default_settings: ClassSettings,
+}
+
+impl Simulation {
+    pub fn new(seed: u64) -> Simulation {
+        return Simulation {
+            ships: Vec::new(),
+            bullets: Vec::new(),
+            tick: 0,
+            seed,
+            rng: Rand64::new(seed as u128),
+            next_id: 1,
+            radar_noise: 1.0,
+            team_settings: Vec::new(),
+            default_settings: ClassSettings::new(),
+        };
+    }
+
+    pub fn current_tick(&self) -> u32 {
+        return self.tick;
+    }
+
+    pub fn current_time(&self) -> f64 {
+        return self.tick as f64 * TICK_LENGTH;
+    }
+
+    pub fn set_radar_noise(&mut self, mult: f64) { // 0 gives perfect radar returns
+        self.radar_noise = mult;
+    }
+
+    pub fn set_team_settings(&mut self, team: usize, settings: ClassSettings) { // picked up by each ship on its first tick, so set them before running
+        self.team_settings.retain(|(configured, _)| *configured != team);
+        self.team_settings.push((team, settings));
+    }
+
+    // Ships with a controller run fleet_v1, everything else (targets, asteroids) just drifts
+    pub fn add_ship(&mut self, class: Class, team: usize, position: Vec2, velocity: Vec2, heading: f64) -> u32 {
+        let pilot = if has_controller(class) { Pilot::Fleet(None) } else { Pilot::Idle };
+        return self.add_ship_with_pilot(class, team, position, velocity, heading, pilot);
+    }
+
+    pub fn add_ship_with_pilot(&mut self, class: Class, team: usize, position: Vec2, velocity: Vec2, heading: f64, pilot: Pilot) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.ships.push(SimShip {
+            id,
+            class,
+            team,
+            position,
+            velocity,
+            heading: heading.rem_euclid(TAU),
+            angular_velocity: 0.0,
+            health: class.default_stats().max_health,
+            fuel: initial_fuel(class),
+            damage_taken: 0.0,
+            hits: [0; 4],
+            death_tick: None,
+            weapons: loadout::loadout(class),
+            pilot,
+            acceleration: Vec2::zero(),
+            torque: 0.0,
+            aim: [heading; 4],
+            fire: [false; 4],
+            explode: false,
+            abilities: 0,
+            reload: [0; 4],
+            boost_ticks: 0,
+            boost_reload: 0,
+            shield_ticks: 0,
+            shield_reload: 0,
+            radar: Radar { heading, width: TAU / 10.0, min_distance: 0.0, max_distance: f64::MAX, contact: None },
+            radios: [Radio::default(); MAX_RADIOS],
+            selected_radio: 0.0,
+        });
+        return id;
+    }
+
+    pub fn ship(&self, id: u32) -> Option<&SimShip> {
+        return self.ships.iter().find(|ship| ship.id == id);
+    }
+
+    pub fn ship_mut(&mut self, id: u32) -> Option<&mut SimShip> {
+        return self.ships.iter_mut().find(|ship| ship.id == id);
+    }
+
+    pub fn alive_count(&self, team: usize) -> usize { // munitions don't count
+        return self.ships.iter().filter(|ship| ship.team == team && ship.alive() && !is_munition(ship.class)).count();
+    }
+
+    pub fn winner(&self) -> Option<usize> { // the only team with ships left, if the fight is over
+        let mut teams = self.ships.iter()
+            .filter(|ship| ship.alive() && !is_munition(ship.class) && ship.class != Class::Asteroid)
+            .map(|ship| ship.team);
+        let first = teams.next()?;
+        if teams.all(|team| team == first) { Some(first) } else { None }
+    }
+
+    pub fn run(&mut self, max_ticks: u32) -> Option<usize> { // run until there is a winner or the time is up
+        for _ in 0..max_ticks {
+            if let Some(team) = self.winner() {
+                return Some(team);
+            }
+            self.tick();
+        }
+        return self.winner();
+    }
+
+    pub fn tick(&mut self) {
+        // radar returns reflect the beam each ship set up last tick
+        self.scan();
+        self.deliver_radio();
+
+        for ship in self.ships.iter_mut().filter(|ship| ship.alive()) {
+            let settings = self.team_settings.iter().find(|(team, _)| *team == ship.team).map_or(&self.default_settings, |(_, settings)| settings);
+            ship.run_pilot(self.tick, self.seed, settings);
+        }
+
+        self.step_ships();
+        self.fire_weapons();
+        self.step_bullets();
+        self.detonate_munitions();
+
+        self.tick += 1;
+    }
+
+    fn deliver_radio(&mut self) {
+        let mut sent = Vec::new();
+        for ship in self.ships.iter_mut() {
+            // only the radios the class is fitted with work
+            for radio in ship.radios.iter_mut().take(radio::radios(ship.class)) {
+                if let Some(message) = radio.sent.take() {
+                    if ship.death_tick.is_none() {
+                        sent.push((ship.team, radio.channel, ship.id, message));
+                    }
+                }
+            }
+        }
+        for ship in self.ships.iter_mut() {
+            for radio in ship.radios.iter_mut().take(radio::radios(ship.class)) {
+                // one message per channel and tick, never our own
+                radio.received = sent.iter()
+                    .find(|(team, channel, sender, _)| *team == ship.team && *channel == radio.channel && *sender != ship.id)
+                    .map(|(_, _, _, message)| *message);
+            }
+        }
+    }
+
+    fn step_ships(&mut self) {
+        for ship in self.ships.iter_mut().filter(|ship| ship.alive()) {
+            let abilities = ActiveAbilities(ship.abilities);
+            ship.boost_reload = ship.boost_reload.saturating_sub(1);
+            ship.shield_reload = ship.shield_reload.saturating_sub(1);
+            ship.boost_ticks = ship.boost_ticks.saturating_sub(1);
+            ship.shield_ticks = ship.shield_ticks.saturating_sub(1);
+            if abilities.get_ability(Ability::Boost) && ship.boost_reload == 0 && (ship.class == Class::Fighter || ship.class == Class::Missile) {
+                ship.boost_ticks = BOOST_TICKS;
+                ship.boost_reload = BOOST_RELOAD_TICKS;
+            }
+            if abilities.get_ability(Ability::Shield) && ship.shield_reload == 0 && ship.class == Class::Cruiser {
+                ship.shield_ticks = SHIELD_TICKS;
+                ship.shield_reload = SHIELD_RELOAD_TICKS;
+            }
+
+            let mut acceleration = ship.acceleration;
+            if ship.boost_ticks > 0 {
+                acceleration.x += BOOST_ACCELERATION;
+            }
+            let delta_v = acceleration.length() * TICK_LENGTH;
+            if delta_v > ship.fuel {
+                acceleration = Vec2::zero();
+            } else {
+                ship.fuel -= delta_v;
+            }
+
+            ship.velocity += acceleration.rotate(ship.heading) * TICK_LENGTH;
+            ship.position += ship.velocity * TICK_LENGTH;
+            ship.angular_velocity += ship.torque * TICK_LENGTH;
+            ship.heading = (ship.heading + ship.angular_velocity * TICK_LENGTH).rem_euclid(TAU);
+            for reload in ship.reload.iter_mut() {
+                *reload = reload.saturating_sub(1);
+            }
+        }
+    }
+
+    fn fire_weapons(&mut self) {
+        let mut launched = Vec::new();
+        for ship in self.ships.iter_mut().filter(|ship| ship.alive()) {
+            for weapon in ship.weapons.iter() {
+                let i = weapon.slot;
+                if !ship.fire[i] || ship.reload[i] > 0 {
+                    continue;
+                }
+                let direction = if weapon.turret { ship.aim[i] } else { ship.heading };
+                if weapon.turret && !weapon.covers(ship.heading, direction) { // the hull is in the way
+                    continue;
+                }
+                ship.reload[i] = weapon.reload_ticks;
+                let origin = ship.position + weapon.offset.rotate(ship.heading);
+                match weapon.kind {
+                    WeaponKind::Gun { damage, lifetime, pellets, spread } => {
+                        for pellet in 0..pellets {
+                            let offset = if pellets > 1 { spread * (pellet as f64 / (pellets - 1) as f64 - 0.5) } else { 0.0 };
+                            self.bullets.push(Bullet {
+                                team: ship.team,
+                                shooter: (ship.id, i),
+                                position: origin,
+                                velocity: ship.velocity + utility::get_dir_from_heading(direction + offset) * weapon.speed,
+                                damage,
+                                expire_tick: self.tick + (lifetime / TICK_LENGTH) as u32,
+                            });
+                        }
+                    }
+                    WeaponKind::Launcher(class) => {
+                        launched.push((class, ship.team, origin, ship.velocity + utility::get_dir_from_heading(direction) * weapon.speed, direction));
+                    }
+                }
+            }
+        }
+        for (class, team, position, velocity, heading) in launched {
+            self.add_ship(class, team, position, velocity, heading);
+        }
+    }
+
+    fn step_bullets(&mut self) {
+        let tick = self.tick;
+        let ships = &mut self.ships;
+        let mut hits = Vec::new();
+        self.bullets.retain_mut(|bullet| {
+            if tick >= bullet.expire_tick {
+                return false;
+            }
+            for ship in ships.iter_mut().filter(|ship| ship.alive() && ship.team != bullet.team) {
+                // closest approach of the bullet to the ship during this tick
+                let relative_position = bullet.position - ship.position;
+                let relative_motion = (bullet.velocity - ship.velocity) * TICK_LENGTH;
+                let t = (-relative_position.dot(relative_motion) / relative_motion.dot(relative_motion)).clamp(0.0, 1.0);
+                if (relative_position + relative_motion * t).length() < radius(ship.class) {
+                    Self::damage(ship, bullet.damage, tick);
+                    hits.push(bullet.shooter);
+                    return false;
+                }
+            }
+            bullet.position += bullet.velocity * TICK_LENGTH;
+            return true;
+        });
+        for (shooter, slot) in hits {
+            if let Some(ship) = ships.iter_mut().find(|ship| ship.id == shooter) {
+                ship.hits[slot] += 1;
+            }
+        }
+    }
+
+    fn detonate_munitions(&mut self) {
+        let mut explosions = Vec::new();
+        for ship in self.ships.iter_mut().filter(|ship| ship.alive()) {
+            if let Some((damage, blast_radius)) = warhead(ship.class) {
+                if ship.explode {
+                    explosions.push((ship.team, ship.position, damage, blast_radius));
+                    ship.death_tick = Some(self.tick);
+                }
+            }
+        }
+        // munitions also go off on contact
+        for i in 0..self.ships.len() {
+            let (team, position, class) = (self.ships[i].team, self.ships[i].position, self.ships[i].class);
+            if !self.ships[i].alive() || warhead(class).is_none() {
+                continue;
+            }
+            let hit = self.ships.iter().any(|other| other.alive() && other.team != team && (other.position - position).length() < radius(other.class) + radius(class));
+            if hit {
+                let (damage, blast_radius) = warhead(class).unwrap();
+                explosions.push((team, position, damage, blast_radius));
+                self.ships[i].death_tick = Some(self.tick);
+            }
+        }
+        for (team, position, damage, blast_radius) in explosions {
+            for ship in self.ships.iter_mut().filter(|ship| ship.alive() && ship.team != team) {
+                let distance = ((ship.position - position).length() - radius(ship.class)).max(0.0);
+                if distance < blast_radius {
+                    Self::damage(ship, damage * (1.0 - distance / blast_radius), self.tick);
+                }
+            }
+        }
+    }
+
+    fn damage(ship: &mut SimShip, damage: f64, tick: u32) {
+        if ship.shield_ticks > 0 {
+            return;
+        }
+        ship.health -= damage;
+        ship.damage_taken += damage;
+        if ship.health <= 0.0 {
+            ship.death_tick = Some(tick);
+        }
+    }
+
+    fn scan(&mut self) {
+        let mut contacts = Vec::with_capacity(self.ships.len());
+        for ship in self.ships.iter() {
+            let radar = ship.radar;
+            let mut best: Option<Contact> = None;
+            for other in self.ships.iter().filter(|other| other.alive() && other.team != ship.team) {
+                let relative_position = other.position - ship.position;
+                let distance = relative_position.length();
+                if distance < radar.min_distance || distance > radar.max_distance
+                    || angle_diff(radar.heading, relative_position.angle()).abs() > radar.width / 2.0 {
+                    continue;
+                }
+                let rssi = RADAR_POWER * radar_cross_section(other.class) / (radar.width * distance * distance).max(1e-9);
+                let snr = 10.0 * rssi.log10();
+                if snr > 0.0 && best.map_or(true, |best| snr > best.snr) {
+                    best = Some(Contact { class: other.class, position: other.position, velocity: other.velocity, rssi, snr });
+                }
+            }
+            contacts.push(best);
+        }
+        for (ship, contact) in self.ships.iter_mut().zip(contacts) {
+            ship.radar.contact = contact.map(|mut contact| {
+                let error = (contact.position - ship.position).length() * RADAR_NOISE_MULT * self.radar_noise * 10f64.powf(-contact.snr / 20.0);
+                contact.position += Self::gaussian(&mut self.rng) * error;
+                contact.velocity += Self::gaussian(&mut self.rng) * error * 0.1;
+                contact
+            });
+        }
+    }
+
+    fn gaussian(rng: &mut Rand64) -> Vec2 { // Box-Muller
+        let r = (-2.0 * (1.0 - rng.rand_float()).ln()).sqrt();
+        let angle = TAU * rng.rand_float();
+        return vec2(r * angle.cos(), r * angle.sin());
+    }
+}
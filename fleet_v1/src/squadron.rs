@@ -0,0 +1,113 @@
+use oort_api::prelude::*;
+
+use crate::fleet::FleetPicture;
+use crate::io::ShipIo;
+use crate::radar::TrackTable;
+use crate::radio::{DataLink, Packet, RadioMessage, TargetAssignment, TargetReport};
+use crate::settings::Settings;
+use crate::targeting;
+
+// Fighters split the enemies between them: the fighter with the lowest id leads and hands out targets over radio.
+// Without a leader (or a fresh assignment) every fighter picks its own target, avoiding the ones friends were sent after.
+pub struct Squadron {
+    settings: Settings,
+    assignment: Option<(u32, u32)>, // (track id, tick received)
+    engaged: Vec<(u32, u32)>, // tracks assigned to others, (track id, tick received)
+    plan: Vec<(u32, u32)>, // leader only: (fighter id, track id)
+    send_cursor: usize,
+}
+
+impl Squadron {
+    pub fn new(settings: &Settings) -> Squadron {
+        return Squadron { settings: *settings, assignment: None, engaged: Vec::new(), plan: Vec::new(), send_cursor: 0 };
+    }
+
+    pub fn update(&mut self, io: &impl ShipIo, packets: &[Packet], fleet: &FleetPicture, table: &mut TrackTable) { // CALL ONCE PER FRAME after the fleet picture
+        for packet in packets {
+            if let RadioMessage::TargetAssignment(assignment) = packet.message {
+                // the leader's track ids mean nothing to us, find the target by where it is
+                let report = TargetReport { class: assignment.class, position: assignment.position, velocity: assignment.velocity, tick: io.current_tick().saturating_sub(1) };
+                let (position_variance, velocity_variance) = fleet.report_variance(io, packet.sender, &report);
+                let id = table.ingest_report(io, assignment.class, assignment.position + assignment.velocity * TICK_LENGTH, assignment.velocity, position_variance, velocity_variance);
+                if assignment.assignee == io.id() {
+                    self.assignment = Some((id, io.current_tick()));
+                } else {
+                    self.engaged.retain(|(track, _)| *track != id);
+                    self.engaged.push((id, io.current_tick()));
+                }
+            }
+        }
+        let assignment_timeout = self.settings.assignment_timeout;
+        let is_fresh = |tick: u32| io.current_tick().saturating_sub(tick) as f64 * TICK_LENGTH <= assignment_timeout;
+        self.assignment = self.assignment.filter(|(id, tick)| is_fresh(*tick) && table.get(*id).is_some());
+        self.engaged.retain(|(id, tick)| is_fresh(*tick) && table.get(*id).is_some());
+    }
+
+    pub fn assigned_target(&self) -> Option<u32> {
+        return self.assignment.map(|(id, _)| id);
+    }
+
+    pub fn engaged(&self) -> Vec<u32> { // tracks friends were sent after
+        return self.engaged.iter().map(|(id, _)| *id).collect();
+    }
+
+    pub fn is_leader(&self, io: &impl ShipIo, fleet: &FleetPicture) -> bool {
+        return fleet.friends().filter(|friend| friend.class == Class::Fighter).all(|friend| friend.id > io.id());
+    }
+
+    pub fn lead(&mut self, io: &impl ShipIo, fleet: &FleetPicture, table: &TrackTable, link: &mut DataLink) { // CALL ONCE PER FRAME, does nothing unless we lead
+        if !self.is_leader(io, fleet) {
+            self.plan.clear();
+            return;
+        }
+        let mut fighters: Vec<(u32, Vec2)> = vec![(io.id(), io.position())];
+        fighters.extend(fleet.friends().filter(|friend| friend.class == Class::Fighter).map(|friend| (friend.id, friend.position(io))));
+        let targets: Vec<_> = table.enemies().collect();
+        if targets.is_empty() || fighters.len() < 2 {
+            self.plan.clear();
+            return;
+        }
+
+        // greedy auction: best fighter/target pairs first, no target gets more than its share of fighters
+        let share = fighters.len().div_ceil(targets.len());
+        let mut bids = Vec::new();
+        for (fighter, position) in fighters.iter() {
+            for radar_track in targets.iter() {
+                let distance = (radar_track.position(io) - *position).length();
+                let mut bid = targeting::class_value(radar_track.class) / (1.0 + distance / self.settings.target_range_scale);
+                if self.plan.contains(&(*fighter, radar_track.id)) {
+                    bid *= self.settings.target_switch_ratio; // keep pairs together unless something is clearly better
+                }
+                bids.push((*fighter, radar_track.id, bid));
+            }
+        }
+        bids.sort_by(|a, b| b.2.total_cmp(&a.2));
+
+        let mut plan: Vec<(u32, u32)> = Vec::new();
+        for (fighter, target, _) in bids {
+            if plan.iter().all(|(assigned, _)| *assigned != fighter) && plan.iter().filter(|(_, assigned)| *assigned == target).count() < share {
+                plan.push((fighter, target));
+            }
+        }
+        self.plan = plan;
+
+        // our own share needs no radio
+        if let Some((_, target)) = self.plan.iter().find(|(fighter, _)| *fighter == io.id()) {
+            self.assignment = Some((*target, io.current_tick()));
+        }
+        let others: Vec<_> = self.plan.iter().filter(|(fighter, _)| *fighter != io.id()).collect();
+        for i in 0..others.len().min(self.settings.assignments_per_tick) {
+            let (fighter, target) = others[(self.send_cursor + i) % others.len()];
+            if let Some(radar_track) = table.get(*target) {
+                link.send(RadioMessage::TargetAssignment(TargetAssignment {
+                    assignee: *fighter,
+                    class: radar_track.class,
+                    position: radar_track.position(io),
+                    velocity: radar_track.velocity(io),
+                }));
+            }
+        }
+        self.send_cursor = self.send_cursor.wrapping_add(self.settings.assignments_per_tick);
+        self.engaged = others.iter().map(|(_, target)| (*target, io.current_tick())).collect();
+    }
+}
@@ -0,0 +1,84 @@
+use oort_api::prelude::*;
+
+use crate::io::ShipIo;
+use crate::radar::{RadarTrack, TrackTable};
+use crate::settings::Settings;
+
+// What our guns can do against a target, used to estimate how long a kill takes
+#[derive(Clone, Copy, Debug)]
+pub struct WeaponProfile {
+    pub bullet_speed: f64, // m/s
+    pub damage_per_second: f64,
+}
+
+pub fn class_value(class: Class) -> f64 { // how much killing one of these is worth
+    return match class {
+        Class::Cruiser => 4.0,
+        Class::Frigate => 3.0,
+        Class::Fighter => 2.0,
+        Class::Target => 1.0,
+        _ => 0.0, // munitions and asteroids are not targets
+    };
+}
+
+pub fn time_to_kill(io: &impl ShipIo, radar_track: &RadarTrack, weapon: &WeaponProfile) -> f64 { // s, bullet flight time plus time to chew through the hull
+    let distance = (radar_track.position(io) - io.position()).length();
+    return distance / weapon.bullet_speed + radar_track.class.default_stats().max_health / weapon.damage_per_second;
+}
+
+pub fn score(io: &impl ShipIo, settings: &Settings, radar_track: &RadarTrack, weapon: &WeaponProfile, engaged: bool) -> f64 { // higher is more worth shooting at
+    let value = class_value(radar_track.class);
+    if value <= 0.0 {
+        return 0.0;
+    }
+    let relative_position = radar_track.position(io) - io.position();
+    let relative_velocity = radar_track.velocity(io) - io.velocity();
+    let distance = relative_position.length();
+    let closing_speed = -relative_velocity.dot(relative_position / distance.max(1.0));
+
+    let range_factor = 1.0 / (1.0 + distance / settings.target_range_scale); // near targets threaten us and are easier to hit
+    let closing_factor = 1.0 + closing_speed.max(0.0) / settings.target_closing_scale;
+    let engaged_factor = if engaged { settings.target_engaged_factor } else { 1.0 };
+
+    return value * range_factor * closing_factor * engaged_factor / time_to_kill(io, radar_track, weapon);
+}
+
+pub struct TargetSelector {
+    settings: Settings,
+    pub weapon: WeaponProfile,
+    current: Option<u32>,
+}
+
+impl TargetSelector {
+    pub fn new(weapon: WeaponProfile, settings: &Settings) -> TargetSelector {
+        return TargetSelector { settings: *settings, weapon, current: None };
+    }
+
+    pub fn current(&self) -> Option<u32> {
+        return self.current;
+    }
+
+    pub fn assign(&mut self, id: u32) { // someone else picked our target, later selections start from it
+        self.current = Some(id);
+    }
+
+    // engaged: tracks other friendly ships are already working on
+    pub fn select(&mut self, io: &impl ShipIo, table: &TrackTable, engaged: &[u32]) -> Option<u32> { // CALL ONCE PER FRAME
+        let score_of = |radar_track: &RadarTrack| score(io, &self.settings, radar_track, &self.weapon, engaged.contains(&radar_track.id));
+
+        let best = table.enemies()
+            .map(|radar_track| (radar_track.id, score_of(radar_track)))
+            .filter(|(_, score)| *score > 0.0)
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        let current = self.current
+            .and_then(|id| table.get(id))
+            .map(|radar_track| (radar_track.id, score_of(radar_track)));
+
+        // only switch for a clearly better target so we don't flip-flop between similar ones
+        self.current = match (current, best) {
+            (Some(current), Some(best)) if best.1 <= current.1 * self.settings.target_switch_ratio => Some(current.0),
+            (_, best) => best.map(|(id, _)| id),
+        };
+        return self.current;
+    }
+}
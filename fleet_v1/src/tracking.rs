@@ -0,0 +1,170 @@
+use oort_api::prelude::*;
+use oort_api::prelude::maths_rs::num::Base;
+
+use crate::io::ShipIo;
+use crate::settings::Settings;
+
+type Matrix = [[f64; 3]; 3];
+
+// Constant acceleration Kalman filter over one target.
+// x and y are filtered independently but see identical noise, so they share one covariance (state order: position, velocity, acceleration).
+pub struct Track {
+    position: Vec2,
+    velocity: Vec2,
+    acceleration: Vec2,
+    covariance: Matrix,
+    last_update_tick: Option<u32>,
+}
+
+impl Track {
+    pub fn new() -> Track {
+        return Track {
+            position: Vec2::zero(),
+            velocity: Vec2::zero(),
+            acceleration: Vec2::zero(),
+            covariance: [[0.0; 3]; 3],
+            last_update_tick: None,
+        };
+    }
+
+    pub fn update(&mut self, io: &impl ShipIo, settings: &Settings, contact: &ScanResult) {
+        let (position_variance, velocity_variance) = measurement_variance(io, settings, contact);
+        self.update_measurement(settings, io.current_tick(), contact.position, contact.velocity, position_variance, velocity_variance);
+    }
+
+    // for measurements that didn't come from our own radar (e.g. radio reports)
+    pub fn update_measurement(&mut self, settings: &Settings, tick: u32, position: Vec2, velocity: Vec2, position_variance: f64, velocity_variance: f64) {
+        if let Some(last_update_tick) = self.last_update_tick {
+            let dt = tick.saturating_sub(last_update_tick) as f64 * TICK_LENGTH;
+            if dt <= settings.track_timeout {
+                self.predict(settings, dt);
+                if self.correct(settings, position, velocity, position_variance, velocity_variance) {
+                    self.last_update_tick = Some(tick);
+                    return;
+                }
+            }
+        }
+        // new, stale or jumped (probably a different contact): start over from this measurement
+        self.position = position;
+        self.velocity = velocity;
+        self.acceleration = Vec2::zero();
+        self.covariance = [
+            [position_variance, 0.0, 0.0],
+            [0.0, velocity_variance, 0.0],
+            [0.0, 0.0, settings.track_initial_acceleration * settings.track_initial_acceleration],
+        ];
+        self.last_update_tick = Some(tick);
+    }
+
+    fn predict(&mut self, settings: &Settings, dt: f64) {
+        let transition = [
+            [1.0, dt, 0.5 * dt * dt],
+            [0.0, 1.0, dt],
+            [0.0, 0.0, 1.0],
+        ];
+        self.position += self.velocity * dt + 0.5 * self.acceleration * dt * dt;
+        self.velocity += self.acceleration * dt;
+
+        // white noise jerk
+        let q = settings.track_jerk_noise;
+        let (dt2, dt3) = (dt * dt, dt * dt * dt);
+        let process_noise = [
+            [q * dt3 * dt2 / 20.0, q * dt2 * dt2 / 8.0, q * dt3 / 6.0],
+            [q * dt2 * dt2 / 8.0, q * dt3 / 3.0, q * dt2 / 2.0],
+            [q * dt3 / 6.0, q * dt2 / 2.0, q * dt],
+        ];
+        let propagated = multiply(&multiply(&transition, &self.covariance), &transpose(&transition));
+        for (i, row) in self.covariance.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = propagated[i][j] + process_noise[i][j];
+            }
+        }
+    }
+
+    fn correct(&mut self, settings: &Settings, position: Vec2, velocity: Vec2, position_variance: f64, velocity_variance: f64) -> bool { // false if the measurement is outside the gate
+        let p = self.covariance;
+        // innovation covariance S = H P Hᵀ + R for the position and velocity measurement
+        let s = [[p[0][0] + position_variance, p[0][1]], [p[1][0], p[1][1] + velocity_variance]];
+        let determinant = s[0][0] * s[1][1] - s[0][1] * s[1][0];
+        let s_inverse = [[s[1][1] / determinant, -s[0][1] / determinant], [-s[1][0] / determinant, s[0][0] / determinant]];
+
+        let position_innovation = position - self.position;
+        let velocity_innovation = velocity - self.velocity;
+        if position_innovation.length() > settings.track_gate_sigmas * s[0][0].sqrt() {
+            return false;
+        }
+
+        // K = P Hᵀ S⁻¹ (3x2)
+        let mut gain = [[0.0; 2]; 3];
+        for (i, row) in gain.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = p[i][0] * s_inverse[0][j] + p[i][1] * s_inverse[1][j];
+            }
+        }
+        self.position += position_innovation * gain[0][0] + velocity_innovation * gain[0][1];
+        self.velocity += position_innovation * gain[1][0] + velocity_innovation * gain[1][1];
+        self.acceleration += position_innovation * gain[2][0] + velocity_innovation * gain[2][1];
+
+        // P = (I - K H) P
+        for (i, row) in self.covariance.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = p[i][j] - gain[i][0] * p[0][j] - gain[i][1] * p[1][j];
+            }
+        }
+        return true;
+    }
+
+    pub fn position(&self) -> Vec2 {
+        return self.position;
+    }
+
+    pub fn velocity(&self) -> Vec2 {
+        return self.velocity;
+    }
+
+    pub fn acceleration(&self) -> Vec2 {
+        return self.acceleration;
+    }
+
+    pub fn predicted_position(&self, dt: f64) -> Vec2 {
+        return self.position + self.velocity * dt + 0.5 * self.acceleration * dt * dt;
+    }
+
+    pub fn predicted_velocity(&self, dt: f64) -> Vec2 {
+        return self.velocity + self.acceleration * dt;
+    }
+
+    pub fn last_update_tick(&self) -> Option<u32> {
+        return self.last_update_tick;
+    }
+
+    pub fn position_uncertainty(&self) -> f64 { // standard deviation (m)
+        return self.covariance[0][0].sqrt();
+    }
+}
+
+pub fn measurement_variance(io: &impl ShipIo, settings: &Settings, contact: &ScanResult) -> (f64, f64) { // radar noise grows with range and shrinks with signal strength
+    let position_error = f64::max((contact.position - io.position()).length() * settings.radar_noise_mult * 10f64.powf(-contact.snr / 20.0), settings.radar_min_noise);
+    let velocity_error = position_error * settings.radar_velocity_noise_mult;
+    return (position_error * position_error, velocity_error * velocity_error);
+}
+
+fn multiply(a: &Matrix, b: &Matrix) -> Matrix {
+    let mut result = [[0.0; 3]; 3];
+    for (i, row) in result.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    return result;
+}
+
+fn transpose(a: &Matrix) -> Matrix {
+    let mut result = [[0.0; 3]; 3];
+    for (i, row) in result.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = a[j][i];
+        }
+    }
+    return result;
+}
@@ -0,0 +1,57 @@
+use oort_api::prelude::*;
+use oort_api::prelude::maths_rs::num::Base;
+
+use fleet_v1::ballistics;
+use fleet_v1::mock::MockIo;
+use fleet_v1::settings::Settings;
+
+const BULLET_SPEED: f64 = 1000.0; // fighter gun
+
+// Where a bullet fired at the intercept's aim point is after its time of flight
+fn bullet_position(io: &MockIo, intercept: &ballistics::Intercept, bullet_speed: f64) -> Vec2 {
+    let direction = (intercept.aim_point - io.position).normalize();
+    io.position + (io.velocity + direction * bullet_speed) * intercept.time
+}
+
+#[test]
+fn intercept_hits_a_crossing_target() {
+    let settings = Settings::new();
+    let mut io = MockIo::new(Class::Fighter);
+    io.velocity = vec2(50.0, -20.0);
+    let (target, target_velocity) = (vec2(2000.0, 500.0), vec2(-100.0, 300.0));
+
+    let intercept = ballistics::intercept(&io, &settings, target, target_velocity, Vec2::zero(), BULLET_SPEED, settings.enemy_size, io.position).unwrap();
+    assert!((intercept.target_position - (target + target_velocity * intercept.time)).length() < 1e-6);
+    assert!((bullet_position(&io, &intercept, BULLET_SPEED) - intercept.target_position).length() < 1e-6);
+}
+
+#[test]
+fn intercept_hits_an_accelerating_target() {
+    let settings = Settings::new();
+    let io = MockIo::new(Class::Fighter);
+    let (target, target_velocity, target_acceleration) = (vec2(-1500.0, 1500.0), vec2(200.0, 0.0), vec2(0.0, -60.0));
+
+    let intercept = ballistics::intercept(&io, &settings, target, target_velocity, target_acceleration, BULLET_SPEED, settings.enemy_size, io.position).unwrap();
+    let miss = (bullet_position(&io, &intercept, BULLET_SPEED) - intercept.target_position).length();
+    assert!(miss < settings.enemy_size * settings.prediction_accuracy_factor);
+}
+
+#[test]
+fn intercept_reports_no_solution_when_the_target_outruns_the_bullets() {
+    let settings = Settings::new();
+    let io = MockIo::new(Class::Fighter);
+    let running = ballistics::intercept(&io, &settings, vec2(1000.0, 0.0), vec2(1500.0, 0.0), Vec2::zero(), BULLET_SPEED, settings.enemy_size, io.position);
+    assert!(running.is_none());
+
+    // radar noise turned into a huge acceleration estimate used to hang the old iterative lead
+    let accelerating = ballistics::intercept(&io, &settings, vec2(2000.0, -800.0), vec2(0.0, 50.0), vec2(0.0, 3000.0), BULLET_SPEED, settings.enemy_size, io.position);
+    assert!(accelerating.is_none());
+}
+
+#[test]
+fn intercept_gives_up_beyond_max_time() {
+    let settings = Settings::new();
+    let io = MockIo::new(Class::Fighter);
+    let far = ballistics::intercept(&io, &settings, vec2(BULLET_SPEED * settings.max_intercept_time * 2.0, 0.0), Vec2::zero(), Vec2::zero(), BULLET_SPEED, settings.enemy_size, io.position);
+    assert!(far.is_none());
+}
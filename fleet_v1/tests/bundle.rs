@@ -0,0 +1,30 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn bundled_fleet_builds_standalone() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let project = manifest_dir.join("target").join("bundle-check");
+    fs::create_dir_all(project.join("src")).unwrap();
+
+    let status = Command::new(env!("CARGO_BIN_EXE_bundle")).arg(project.join("src").join("lib.rs")).status().unwrap();
+    assert!(status.success());
+    let bundled = fs::read_to_string(project.join("src").join("lib.rs")).unwrap();
+    assert!(!bundled.contains("mod sim"));
+    assert!(!bundled.contains("MockIo") && !bundled.contains("test-support"));
+
+    // Oort looks for `Ship` at the top of the file
+    fs::write(project.join("src").join("lib.rs"), bundled + "\npub type TopLevelShip = Ship;\n").unwrap();
+    fs::write(project.join("Cargo.toml"), "[package]\nname = \"fleet_v1_bundle\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\noort_api = \"0.80.0\"\n\n[workspace]\n").unwrap();
+    // reuse our lock file so the bundle builds against the same oort_api without touching the network
+    fs::copy(manifest_dir.join("Cargo.lock"), project.join("Cargo.lock")).unwrap();
+
+    let output = Command::new(std::env::var("CARGO").unwrap_or("cargo".to_string()))
+        .args(["build", "--offline", "--quiet"])
+        .current_dir(&project)
+        .env("CARGO_TARGET_DIR", project.join("target"))
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
@@ -0,0 +1,35 @@
+// Fixtures shared by the integration tests, each test file only uses some of them
+#![allow(dead_code)]
+
+use oort_api::prelude::*;
+
+use fleet_v1::controller::Target;
+use fleet_v1::mock::MockIo;
+use fleet_v1::radio::{Packet, RadioMessage, Status};
+use fleet_v1::sim::Pilot;
+
+pub fn contact(class: Class, position: Vec2, velocity: Vec2) -> ScanResult { // a clean radar return
+    ScanResult { class, position, velocity, rssi: 0.0, snr: 20.0 }
+}
+
+pub fn enemy(position: Vec2) -> ScanResult { // a fighter sitting still
+    contact(Class::Fighter, position, vec2(0.0, 0.0))
+}
+
+pub fn target(position: Vec2, velocity: Vec2, acceleration: Vec2) -> Target { // a fighter track, predicted to now
+    Target { id: 1, class: Class::Fighter, position, velocity, acceleration }
+}
+
+pub fn missile(velocity: Vec2) -> MockIo { // in flight from the origin
+    let mut io = MockIo::new(Class::Missile);
+    io.velocity = velocity;
+    io
+}
+
+pub fn heartbeat(sender: u32, class: Class, position: Vec2) -> Packet { // a friend at full health
+    Packet { sender, message: RadioMessage::Status(Status { class, health: class.default_stats().max_health, position, velocity: vec2(0.0, 0.0) }) }
+}
+
+pub fn inert() -> Pilot { // a ship that does nothing at all
+    Pilot::Scripted(Box::new(|| {}))
+}
@@ -0,0 +1,64 @@
+use oort_api::prelude::*;
+
+use fleet_v1::controller::{self, Core, State};
+use fleet_v1::mock::MockIo;
+use fleet_v1::loadout;
+use fleet_v1::settings::Settings;
+
+mod common;
+
+fn core() -> Core {
+    Core::new(loadout::weapon_profile(&loadout::loadout(Class::Fighter)), &Settings::new())
+}
+
+fn track(io: &mut MockIo, core: &mut Core, position: Vec2) -> u32 { // a few ticks of radar returns
+    let mut id = 0;
+    for _ in 0..10 {
+        id = core.radar.table.ingest(io, &common::contact(Class::Fighter, position, vec2(0.0, 0.0)));
+        io.current_tick += 1;
+    }
+    io.current_tick -= 1;
+    id
+}
+
+#[test]
+fn nothing_selected_is_search() {
+    let io = MockIo::new(Class::Fighter);
+    assert_eq!(controller::next_state(&io, &core(), true, f64::INFINITY), State::Search);
+}
+
+#[test]
+fn radio_only_targets_are_acquired_first() {
+    let io = MockIo::new(Class::Fighter);
+    let mut core = core();
+    core.target = Some(core.radar.table.ingest_report(&io, Class::Fighter, vec2(3000.0, 0.0), vec2(0.0, 0.0), 100.0, 10.0));
+    assert_eq!(controller::next_state(&io, &core, true, f64::INFINITY), State::Acquire);
+}
+
+#[test]
+fn good_tracks_are_engaged_inside_the_engage_range() {
+    let mut io = MockIo::new(Class::Fighter);
+    let mut core = core();
+    core.target = Some(track(&mut io, &mut core, vec2(3000.0, 0.0)));
+    assert_eq!(controller::next_state(&io, &core, true, 2000.0), State::Track);
+    assert_eq!(controller::next_state(&io, &core, true, 4000.0), State::Engage);
+}
+
+#[test]
+fn targets_without_updates_are_lost() {
+    let mut io = MockIo::new(Class::Fighter);
+    let mut core = core();
+    core.target = Some(track(&mut io, &mut core, vec2(3000.0, 0.0)));
+    io.current_tick += (Settings::new().track_lost_age / TICK_LENGTH) as u32 + 1;
+    assert_eq!(controller::next_state(&io, &core, true, f64::INFINITY), State::Lost);
+}
+
+#[test]
+fn only_evading_classes_evade() {
+    let mut io = MockIo::new(Class::Fighter);
+    let mut core = core();
+    core.target = Some(track(&mut io, &mut core, vec2(3000.0, 0.0)));
+    core.radar.table.ingest(&io, &ScanResult { velocity: vec2(-500.0, 0.0), ..common::contact(Class::Missile, vec2(1000.0, 0.0), vec2(0.0, 0.0)) });
+    assert_eq!(controller::next_state(&io, &core, true, f64::INFINITY), State::Evade);
+    assert_eq!(controller::next_state(&io, &core, false, f64::INFINITY), State::Engage);
+}
@@ -0,0 +1,67 @@
+use oort_api::prelude::*;
+
+use fleet_v1::delta_v::{self, Phase};
+use fleet_v1::mock::MockIo;
+use fleet_v1::settings::Settings;
+use fleet_v1::sim::Simulation;
+
+mod common;
+
+fn missile(fuel: f64) -> MockIo { // closing in at 1 km/s
+    MockIo { fuel, ..common::missile(vec2(1000.0, 0.0)) }
+}
+
+#[test]
+fn munitions_boost_until_only_the_reserve_is_left() {
+    let settings = Settings::new();
+    let reserve = delta_v::reserve(&missile(2000.0), &settings);
+    assert!(reserve > 0.0 && reserve < 2000.0);
+    assert_eq!(delta_v::phase(&missile(2000.0), &settings, &common::target(vec2(10000.0, 0.0), vec2(0.0, 0.0), vec2(0.0, 0.0))), Phase::Boost);
+    assert_eq!(delta_v::phase(&missile(reserve), &settings, &common::target(vec2(10000.0, 0.0), vec2(0.0, 0.0), vec2(0.0, 0.0))), Phase::Coast);
+}
+
+fn boost_ticks(range: f64) -> (u32, f64) { // (ticks of boost, fuel left after it) flying a missile straight at a target that sits still
+    let settings = Settings::new();
+    let mut io = missile(2000.0);
+    io.velocity = vec2(100.0, 0.0); // just launched
+    let target = common::target(vec2(range, 0.0), vec2(0.0, 0.0), vec2(0.0, 0.0));
+    let mut ticks = 0;
+    while delta_v::phase(&io, &settings, &target) == Phase::Boost {
+        io.velocity.x += io.max_forward_acceleration * TICK_LENGTH;
+        io.fuel -= io.max_forward_acceleration * TICK_LENGTH;
+        io.position += io.velocity * TICK_LENGTH;
+        ticks += 1;
+    }
+    (ticks, io.fuel)
+}
+
+#[test]
+fn long_shots_boost_less_to_keep_fuel_for_steering() {
+    let reserve = delta_v::reserve(&missile(2000.0), &Settings::new());
+    let (short_ticks, short_fuel) = boost_ticks(8000.0);
+    let (long_ticks, long_fuel) = boost_ticks(20000.0);
+    assert!(long_ticks < short_ticks, "{} vs {} ticks", long_ticks, short_ticks);
+    assert!(long_fuel > short_fuel + 100.0, "{} vs {} left", long_fuel, short_fuel);
+    assert!(short_fuel > reserve);
+}
+
+#[test]
+fn the_reserve_is_spent_at_the_end() {
+    let settings = Settings::new();
+    let close = common::target(vec2(1000.0 * settings.terminal_time * 0.9, 0.0), vec2(0.0, 0.0), vec2(0.0, 0.0));
+    assert_eq!(delta_v::time_to_go(&missile(0.0), &close), settings.terminal_time * 0.9);
+    assert_eq!(delta_v::phase(&missile(100.0), &settings, &close), Phase::Terminal);
+    // not closing in at all, there is no end in sight
+    assert_eq!(delta_v::time_to_go(&missile(0.0), &common::target(vec2(-1000.0, 0.0), vec2(0.0, 0.0), vec2(0.0, 0.0))), f64::INFINITY);
+}
+
+#[test]
+fn long_shots_keep_fuel_for_the_intercept() {
+    let mut sim = Simulation::new(1);
+    let missile = sim.add_ship(Class::Missile, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), 0.0);
+    let target = sim.add_ship(Class::Target, 1, vec2(15000.0, 0.0), vec2(0.0, 150.0), 0.0);
+    while sim.current_time() < 30.0 && sim.ship(missile).unwrap().alive() {
+        sim.tick();
+    }
+    assert!(!sim.ship(target).unwrap().alive());
+}
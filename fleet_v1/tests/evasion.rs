@@ -0,0 +1,48 @@
+use oort_api::prelude::*;
+
+use fleet_v1::evasion::{self, Maneuver};
+use fleet_v1::mock::MockIo;
+use fleet_v1::radar::TrackTable;
+use fleet_v1::settings::Settings;
+
+mod common;
+
+#[test]
+fn far_missiles_are_beamed() {
+    let settings = Settings::new();
+    let mut io = MockIo::new(Class::Fighter);
+    io.velocity = vec2(0.0, 50.0);
+    let mut table = TrackTable::new(&settings);
+    table.ingest(&io, &common::contact(Class::Missile, vec2(2000.0, 0.0), vec2(-500.0, 0.0)));
+
+    let threat = evasion::incoming_missile(&io, &settings, &table).unwrap();
+    assert_eq!(evasion::evade(&mut io, &settings, threat), Maneuver::Beam);
+    // across the line of sight, keeping our momentum
+    let acceleration = *io.accelerations.last().unwrap();
+    assert!(acceleration.y > 0.0);
+    assert!(acceleration.x.abs() < 1e-6);
+}
+
+#[test]
+fn close_missiles_trigger_a_break_away_from_their_aim_point() {
+    let settings = Settings::new();
+    let mut io = MockIo::new(Class::Fighter);
+    let mut table = TrackTable::new(&settings);
+    // aimed 20 m below us, so break upwards
+    table.ingest(&io, &common::contact(Class::Missile, vec2(300.0, -20.0), vec2(-500.0, 0.0)));
+
+    let threat = evasion::incoming_missile(&io, &settings, &table).unwrap();
+    assert!(evasion::break_time(&settings, Class::Missile) > 0.6);
+    assert_eq!(evasion::evade(&mut io, &settings, threat), Maneuver::Break);
+    assert!(io.accelerations.last().unwrap().y > 0.0);
+}
+
+#[test]
+fn missiles_far_out_or_flying_away_are_ignored() {
+    let settings = Settings::new();
+    let io = MockIo::new(Class::Fighter);
+    let mut table = TrackTable::new(&settings);
+    table.ingest(&io, &common::contact(Class::Missile, vec2(2900.0, 0.0), vec2(-100.0, 0.0))); // 29 s out
+    table.ingest(&io, &common::contact(Class::Missile, vec2(-500.0, 0.0), vec2(-500.0, 0.0)));
+    assert!(evasion::incoming_missile(&io, &settings, &table).is_none());
+}
@@ -0,0 +1,85 @@
+use oort_api::prelude::*;
+
+use fleet_v1::fleet::FleetPicture;
+use fleet_v1::mock::MockIo;
+use fleet_v1::radar::TrackTable;
+use fleet_v1::radio::{DataLink, Packet, RadioMessage, TargetReport};
+use fleet_v1::settings::Settings;
+use fleet_v1::sim::{Pilot, Simulation};
+
+mod common;
+
+fn report(sender: u32, position: Vec2, velocity: Vec2, tick: u32) -> Packet {
+    Packet { sender, message: RadioMessage::TargetReport(TargetReport { class: Class::Fighter, position, velocity, tick }) }
+}
+
+#[test]
+fn reports_are_brought_up_to_date_and_fused() {
+    let settings = Settings::new();
+    let mut io = MockIo::new(Class::Fighter);
+    io.current_tick = 60;
+    let mut table = TrackTable::new(&settings);
+    let mut fleet = FleetPicture::new(&settings);
+
+    // seen half a second ago, moving at 100 m/s
+    fleet.update(&io, &[common::heartbeat(7, Class::Cruiser, vec2(5000.0, 0.0)), report(7, vec2(6000.0, 0.0), vec2(0.0, 100.0), 30)], &mut table);
+    let remote = table.tracks().next().unwrap();
+    assert!(remote.last_radar_tick.is_none());
+    assert!((remote.position(&io) - vec2(6000.0, 50.0)).length() < 1e-3);
+    assert_eq!(fleet.friend(7).unwrap().class, Class::Cruiser);
+
+    // our own radar return lands on the same track
+    let id = remote.id;
+    let contact = common::contact(Class::Fighter, vec2(6000.0, 52.0), vec2(0.0, 100.0));
+    assert_eq!(table.ingest(&io, &contact), id);
+    assert_eq!(table.tracks().count(), 1);
+}
+
+#[test]
+fn reports_from_far_away_or_long_ago_weigh_less() {
+    let settings = Settings::new();
+    let io = MockIo::new(Class::Fighter);
+    let mut fleet = FleetPicture::new(&settings);
+    fleet.update(&io, &[common::heartbeat(1, Class::Cruiser, vec2(5500.0, 0.0)), common::heartbeat(2, Class::Cruiser, vec2(-10000.0, 0.0))], &mut TrackTable::new(&settings));
+
+    let fresh = TargetReport { class: Class::Fighter, position: vec2(6000.0, 0.0), velocity: vec2(0.0, 0.0), tick: 0 };
+    let (near, _) = fleet.report_variance(&io, 1, &fresh);
+    let (far, _) = fleet.report_variance(&io, 2, &fresh);
+    assert!(near < far);
+
+    let mut later = io.clone();
+    later.current_tick = 30;
+    assert!(fleet.report_variance(&later, 1, &fresh).0 > near);
+}
+
+#[test]
+fn lost_friends_are_forgotten() {
+    let settings = Settings::new();
+    let mut io = MockIo::new(Class::Fighter);
+    let mut fleet = FleetPicture::new(&settings);
+    fleet.update(&io, &[common::heartbeat(1, Class::Cruiser, vec2(0.0, 0.0))], &mut TrackTable::new(&settings));
+    io.current_tick = 600;
+    fleet.update(&io, &[], &mut TrackTable::new(&settings));
+    assert_eq!(fleet.friends().count(), 0);
+}
+
+#[test]
+fn fighter_attacks_a_target_only_a_friend_can_see() {
+    let target_position = vec2(0.0, 30000.0); // beyond the fighter's search range
+    let mut sim = Simulation::new(9);
+    let fighter = sim.add_ship(Class::Fighter, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), 0.0);
+    let mut link = DataLink::new(Class::Cruiser);
+    sim.add_ship_with_pilot(Class::Cruiser, 0, vec2(0.0, 26000.0), vec2(0.0, 0.0), 0.0, Pilot::Scripted(Box::new(move || {
+        link.send(RadioMessage::TargetReport(TargetReport { class: Class::Target, position: target_position, velocity: vec2(0.0, 0.0), tick: current_tick() }));
+        link.flush();
+    })));
+    sim.add_ship(Class::Target, 1, target_position, vec2(0.0, 0.0), 0.0);
+
+    for _ in 0..60 * 10 {
+        sim.tick();
+    }
+    let fighter = sim.ship(fighter).unwrap();
+    assert!(fighter.position.y > 1000.0);
+    assert!(fighter.position.x.abs() < fighter.position.y);
+    assert!(sim.ships.iter().any(|ship| ship.class == Class::Missile && ship.team == 0));
+}
@@ -0,0 +1,58 @@
+use oort_api::prelude::*;
+
+use fleet_v1::fuse;
+use fleet_v1::mock::MockIo;
+
+mod common;
+
+#[test]
+fn closest_approach_of_a_crossing_target() {
+    let io = MockIo::new(Class::Missile);
+    let approach = fuse::closest_approach(&io, vec2(1000.0, 20.0), vec2(-500.0, 0.0), vec2(0.0, 0.0));
+    assert!((approach.time - 2.0).abs() < 1e-9);
+    assert!((approach.miss_distance - 20.0).abs() < 1e-9);
+
+    // already moving apart
+    let approach = fuse::closest_approach(&io, vec2(-100.0, 0.0), vec2(-500.0, 0.0), vec2(0.0, 0.0));
+    assert_eq!(approach.time, 0.0);
+    assert_eq!(approach.miss_distance, 100.0);
+}
+
+#[test]
+fn closest_approach_follows_the_target_acceleration() {
+    let io = MockIo::new(Class::Missile);
+    // dead on at constant velocity, the target's acceleration pulls it 0.5 * 20 * 2² = 40 m aside
+    let approach = fuse::closest_approach(&io, vec2(1000.0, 0.0), vec2(-500.0, 0.0), vec2(0.0, 20.0));
+    assert!((approach.time - 2.0).abs() < 0.01);
+    assert!((approach.miss_distance - 40.0).abs() < 0.5);
+}
+
+#[test]
+fn fast_passes_detonate_on_the_closest_tick() {
+    // 3 km/s closing moves 50 m a tick, more than a range fuse can rely on seeing
+    let mut io = MockIo::new(Class::Missile);
+    io.velocity = vec2(3000.0, 0.0);
+    let target = common::target(vec2(1000.0, 20.0), vec2(0.0, 0.0), vec2(0.0, 0.0));
+    let mut bursts = Vec::new();
+    let mut detonated = None;
+    for tick in 0..30 {
+        bursts.push(fuse::burst_distance(&io, &target, 1));
+        if detonated.is_none() && fuse::detonate_now(&io, &target, 30.0) {
+            detonated = Some(tick);
+        }
+        io.position += io.velocity * TICK_LENGTH;
+    }
+    let closest = bursts.iter().cloned().fold(f64::INFINITY, f64::min);
+    assert_eq!(bursts[detonated.unwrap()], closest);
+}
+
+#[test]
+fn wide_passes_do_not_detonate() {
+    let mut io = MockIo::new(Class::Missile);
+    io.velocity = vec2(3000.0, 0.0);
+    let target = common::target(vec2(1000.0, 40.0), vec2(0.0, 0.0), vec2(0.0, 0.0));
+    for _ in 0..30 {
+        assert!(!fuse::detonate_now(&io, &target, 30.0));
+        io.position += io.velocity * TICK_LENGTH;
+    }
+}
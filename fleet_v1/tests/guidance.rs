@@ -0,0 +1,54 @@
+use oort_api::prelude::*;
+
+use fleet_v1::guidance::{self, Law};
+
+mod common;
+
+const N_FACTOR: f64 = 4.0;
+
+#[test]
+fn the_laws_agree_on_a_steady_target() {
+    let crossing = common::target(vec2(3000.0, 0.0), vec2(0.0, 150.0), vec2(0.0, 0.0));
+    let pn = guidance::lateral_acceleration(&common::missile(vec2(500.0, 0.0)), Law::ProportionalNavigation, N_FACTOR, &crossing);
+    for law in [Law::AugmentedProportionalNavigation, Law::ZeroEffortMiss] {
+        assert!((guidance::lateral_acceleration(&common::missile(vec2(500.0, 0.0)), law, N_FACTOR, &crossing) - pn).length() < 1e-9);
+    }
+    // 4 * 500 m/s * (150 / 3000) rad/s, leading the target
+    assert!((pn - vec2(0.0, 100.0)).length() < 1e-9);
+}
+
+#[test]
+fn augmented_laws_lead_an_accelerating_target() {
+    let jinking = common::target(vec2(3000.0, 0.0), vec2(0.0, 0.0), vec2(0.0, -60.0));
+    let pn = guidance::lateral_acceleration(&common::missile(vec2(500.0, 0.0)), Law::ProportionalNavigation, N_FACTOR, &jinking);
+    assert_eq!(pn.length(), 0.0);
+    for law in [Law::AugmentedProportionalNavigation, Law::ZeroEffortMiss] {
+        let lateral = guidance::lateral_acceleration(&common::missile(vec2(500.0, 0.0)), law, N_FACTOR, &jinking);
+        assert!((lateral - vec2(0.0, -120.0)).length() < 1e-9, "{:?}: {}", law, lateral);
+    }
+}
+
+#[test]
+fn opening_targets_are_chased() {
+    let fleeing = common::target(vec2(3000.0, 0.0), vec2(800.0, 300.0), vec2(0.0, 0.0));
+    let command = guidance::command(&common::missile(vec2(500.0, 0.0)), Law::ZeroEffortMiss, N_FACTOR, &fleeing);
+    assert_eq!(command.heading, 0.0);
+    assert_eq!(command.acceleration, vec2(common::missile(vec2(500.0, 0.0)).max_forward_acceleration, 0.0));
+}
+
+#[test]
+fn commands_stay_within_the_thruster_limits() {
+    let mut io = common::missile(vec2(500.0, 0.0));
+    io.heading = PI / 2.0; // nose well off the line of sight
+    let crossing = common::target(vec2(3000.0, 0.0), vec2(0.0, 150.0), vec2(0.0, 0.0));
+    let command = guidance::command(&io, Law::ProportionalNavigation, N_FACTOR, &crossing);
+
+    // the nose goes to the full command: steering plus what is left of the forward thrust down the line of sight
+    let forward = io.max_forward_acceleration;
+    assert!(angle_diff(command.heading, vec2((forward * forward - 100.0 * 100.0).sqrt(), 100.0).angle()).abs() < 1e-9);
+    // meanwhile the thrust is held to the ship frame limits
+    let body = command.acceleration.rotate(-io.heading);
+    assert!(body.x <= forward + 1e-9 && body.x >= -io.max_backward_acceleration - 1e-9);
+    assert!(body.y.abs() <= io.max_lateral_acceleration + 1e-9);
+    assert!(command.acceleration.dot(vec2(1.0, 0.0)) > 0.0);
+}
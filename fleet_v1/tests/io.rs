@@ -0,0 +1,45 @@
+use oort_api::prelude::*;
+use oort_api::prelude::maths_rs::num::Base;
+
+use fleet_v1::mock::MockIo;
+use fleet_v1::settings::Settings;
+use fleet_v1::{ballistics, navigation, radar};
+
+const BULLET_SPEED: f64 = 1000.0; // fighter gun
+
+#[test]
+fn turn_torques_towards_the_angle_and_brakes_on_arrival() {
+    let mut io = MockIo::new(Class::Fighter);
+    navigation::turn(&mut io, 1.0, 0.0);
+    navigation::turn(&mut io, -1.0, 0.0);
+    assert_eq!(io.torques, vec![io.max_angular_acceleration, -io.max_angular_acceleration]);
+
+    // spinning fast towards a nearby heading: brake
+    io.angular_velocity = 5.0;
+    navigation::turn(&mut io, 0.1, 0.0);
+    assert!(*io.torques.last().unwrap() < 0.0);
+}
+
+#[test]
+fn lead_on_a_stationary_target_compensates_own_velocity() {
+    let settings = Settings::new();
+    let mut io = MockIo::new(Class::Fighter);
+    io.velocity = vec2(0.0, 100.0);
+    let target = vec2(1000.0, 0.0);
+
+    let lead = ballistics::intercept(&io, &settings, target, Vec2::zero(), Vec2::zero(), BULLET_SPEED, settings.enemy_size, io.position).unwrap().aim_point;
+    assert!((lead.x - 1000.0).abs() < 1e-6);
+    // bullets drift sideways with us, so aim just over 100 m off for the ~1 s flight
+    assert!((lead.y + 100.0 / 0.99f64.sqrt()).abs() < 1e-6);
+}
+
+#[test]
+fn track_centers_the_radar_on_the_target() {
+    let mut io = MockIo::new(Class::Fighter);
+    io.position = vec2(100.0, 100.0);
+    radar::track(&mut io, &Settings::new(), vec2(100.0, 1100.0), Vec2::zero());
+
+    assert!((io.radar_heading - PI / 2.0).abs() < 1e-9);
+    assert!(io.radar_min_distance < 1000.0 && io.radar_max_distance > 1000.0);
+    assert!(io.radar_width < 0.1);
+}
@@ -0,0 +1,82 @@
+use oort_api::prelude::*;
+
+use fleet_v1::mock::MockIo;
+use fleet_v1::loadout::{self, WeaponKind};
+use fleet_v1::settings::Settings;
+
+#[test]
+fn slots_match_their_position_in_the_table() {
+    for class in [Class::Fighter, Class::Frigate, Class::Cruiser] {
+        for (i, weapon) in loadout::loadout(class).iter().enumerate() {
+            assert_eq!(weapon.slot, i, "{class:?}");
+        }
+    }
+    assert!(loadout::loadout(Class::Missile).is_empty());
+}
+
+#[test]
+fn main_gun_prefers_the_fixed_gun() {
+    let frigate = loadout::loadout(Class::Frigate);
+    let railgun = loadout::main_gun(&frigate).unwrap();
+    assert_eq!(railgun.slot, 0);
+    assert_eq!(railgun.speed, 4000.0);
+
+    // the cruiser only has its flak turret
+    let cruiser = loadout::loadout(Class::Cruiser);
+    let flak = loadout::main_gun(&cruiser).unwrap();
+    assert!(flak.turret);
+    assert_eq!(flak.speed, 2000.0);
+    assert_eq!(cruiser.iter().filter(|weapon| weapon.kind == WeaponKind::Launcher(Class::Torpedo)).count(), 1);
+}
+
+#[test]
+fn weapon_facts() {
+    let fighter = loadout::loadout(Class::Fighter);
+    assert_eq!(fighter[0].range(), 5000.0);
+    assert!((fighter[0].damage_per_second() - 300.0).abs() < 1e-9);
+    assert_eq!(fighter[1].damage_per_second(), 0.0);
+
+    let profile = loadout::weapon_profile(&loadout::loadout(Class::Frigate));
+    assert_eq!(profile.bullet_speed, 4000.0);
+    assert!((profile.damage_per_second - 1200.0).abs() < 1e-9); // railgun and both turrets
+
+    // turret mounts turn with the ship
+    let mut io = MockIo::new(Class::Frigate);
+    io.position = vec2(100.0, 0.0);
+    io.heading = PI / 2.0;
+    let left_turret = loadout::loadout(Class::Frigate)[1];
+    assert!((left_turret.mount_position(&io) - vec2(90.0, 0.0)).length() < 1e-9);
+}
+
+#[test]
+fn capital_ships_hold_a_band_inside_their_gun_range() {
+    let settings = Settings::new();
+    // the frigate's turrets reach 5 km, the cruiser's flak 4 km
+    let frigate = loadout::loadout(Class::Frigate);
+    assert_eq!(loadout::engagement_band(&settings, &frigate, Class::Target), (0.0, 5000.0 * settings.engagement_range_share));
+    let cruiser = loadout::loadout(Class::Cruiser);
+    assert_eq!(loadout::engagement_band(&settings, &cruiser, Class::Target), (0.0, 4000.0 * settings.engagement_range_share));
+
+    // out of a threat's reach if our guns allow it, otherwise as far out as they do
+    let wide = Settings { engagement_range_share: 1.0, threat_range_share: 0.5, ..settings };
+    assert_eq!(loadout::engagement_band(&wide, &frigate, Class::Cruiser), (2000.0, 5000.0));
+    assert_eq!(loadout::engagement_band(&settings, &cruiser, Class::Fighter), (2800.0, 2800.0));
+}
+
+#[test]
+fn frigates_turn_no_further_than_keeps_both_broadside_turrets_bearing() {
+    let frigate = loadout::loadout(Class::Frigate);
+    let (left_turret, right_turret) = (frigate[1], frigate[2]);
+    // the hull masks the far beam
+    assert!(left_turret.covers(0.0, 0.0) && left_turret.covers(0.0, PI / 2.0) && !left_turret.covers(0.0, -PI / 2.0));
+    assert!(right_turret.covers(0.0, 0.0) && !right_turret.covers(0.0, PI / 2.0));
+
+    // nose on the target already does it
+    assert_eq!(loadout::unmasked_heading(&frigate, 0.3, 0.3), 0.3);
+    // the target abeam: swing the nose round until it is in front of both beams
+    let heading = loadout::unmasked_heading(&frigate, PI / 2.0, 0.0);
+    assert!((heading - PI / 4.0).abs() < 0.02, "{}", heading);
+    assert!(left_turret.covers(heading, PI / 2.0) && right_turret.covers(heading, PI / 2.0));
+    // nothing to mask without turrets
+    assert_eq!(loadout::unmasked_heading(&loadout::loadout(Class::Fighter), PI / 2.0, 0.0), 0.0);
+}
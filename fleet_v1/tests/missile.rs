@@ -0,0 +1,38 @@
+use oort_api::prelude::*;
+
+use fleet_v1::sim::Simulation;
+
+#[test]
+fn searching_missiles_hold_the_launch_heading() {
+    let mut sim = Simulation::new(1);
+    let missile = sim.add_ship(Class::Missile, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), 1.0);
+    for _ in 0..120 { // nothing in range to find
+        sim.tick();
+        let ship = sim.ship(missile).unwrap();
+        assert!(angle_diff(ship.heading, 1.0).abs() < 1e-3, "heading {} at tick {}", ship.heading, sim.current_tick());
+        assert!(ship.angular_velocity.abs() < 1e-3);
+    }
+}
+
+#[test]
+fn missiles_launched_facing_away_only_boost_once_pointed_at_the_target() {
+    let mut sim = Simulation::new(1);
+    let target_position = vec2(5000.0, 0.0);
+    let missile = sim.add_ship(Class::Missile, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), PI - 0.3); // the nose has to swing the long way round
+    sim.add_ship(Class::Target, 1, target_position, vec2(0.0, 0.0), 0.0);
+    let max_forward_acceleration = Class::Missile.default_stats().max_forward_acceleration;
+
+    let mut boosted = false;
+    while sim.current_time() < 10.0 && sim.ship(missile).unwrap().alive() {
+        let before = sim.ship(missile).unwrap();
+        let (position, velocity, heading) = (before.position, before.velocity, before.heading);
+        sim.tick();
+        let forward_acceleration = (sim.ship(missile).unwrap().velocity - velocity).dot(vec2(1.0, 0.0).rotate(heading)) / TICK_LENGTH;
+        let off_nose = angle_diff(heading, (target_position - position).angle()).abs();
+        if off_nose > PI / 8.0 {
+            assert!(forward_acceleration <= max_forward_acceleration + 1e-6, "boosting {:.2} rad off the target at tick {}", off_nose, sim.current_tick());
+        }
+        boosted |= forward_acceleration > max_forward_acceleration + 1.0;
+    }
+    assert!(boosted);
+}
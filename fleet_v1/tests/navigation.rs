@@ -0,0 +1,113 @@
+use oort_api::prelude::*;
+
+use fleet_v1::io::OortIo;
+use fleet_v1::mock::MockIo;
+use fleet_v1::navigation::{self, Path};
+use fleet_v1::settings::Settings;
+use fleet_v1::sim::{Pilot, Simulation};
+
+fn fly(class: Class, velocity: Vec2, seconds: f64, mut pilot: impl FnMut() -> Vec2 + 'static) -> Simulation { // a ship at the origin that accelerates as told
+    let mut sim = Simulation::new(1);
+    sim.add_ship_with_pilot(class, 0, vec2(0.0, 0.0), velocity, 0.0, Pilot::Scripted(Box::new(move || accelerate(pilot()))));
+    while sim.current_time() < seconds {
+        sim.tick();
+    }
+    sim
+}
+
+#[test]
+fn max_acceleration_respects_every_axis() {
+    let mut io = MockIo::new(Class::Fighter); // 60 m/s² forward, 30 backward and sideways
+    io.heading = 6.0;
+    for (direction, expected) in [(6.0, 60.0), (6.0 + PI / 2.0, 30.0), (6.0 - PI, 30.0), (0.1, 60.0 / (0.1 - 6.0 + TAU).cos())] {
+        let acceleration = navigation::get_max_acceleration(&io, direction);
+        assert!((acceleration.length() - expected).abs() < 1e-9, "{} -> {}", direction, acceleration.length());
+        assert!(angle_diff(acceleration.angle(), direction).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn ships_arrive_and_stop() {
+    for class in [Class::Fighter, Class::Frigate, Class::Cruiser] {
+        let point = vec2(1500.0, -800.0);
+        let sim = fly(class, vec2(0.0, 0.0), 90.0, move || navigation::arrive(&OortIo, point));
+        let ship = &sim.ships[0];
+        assert!((ship.position - point).length() < 1.0, "{:?} at {}", class, ship.position);
+        assert!(ship.velocity.length() < 0.5, "{:?} still at {} m/s", class, ship.velocity.length());
+    }
+}
+
+#[test]
+fn station_keeping_cancels_the_drift() {
+    let sim = fly(Class::Fighter, vec2(150.0, -80.0), 20.0, || navigation::station_keep(&OortIo, vec2(0.0, 0.0), vec2(0.0, 0.0)));
+    assert!(sim.ships[0].position.length() < 1.0);
+}
+
+#[test]
+fn velocity_matching_keeps_station_on_a_moving_friend() {
+    // a slot 200 m off a cruiser crossing at 40 m/s
+    let cruiser = |time: f64| vec2(1000.0, 0.0) + vec2(0.0, 40.0) * time;
+    let sim = fly(Class::Fighter, vec2(0.0, 0.0), 30.0, move || {
+        let time = current_tick() as f64 * TICK_LENGTH;
+        navigation::station_keep(&OortIo, cruiser(time) + vec2(-200.0, 0.0), vec2(0.0, 40.0))
+    });
+    let ship = &sim.ships[0];
+    assert!((ship.position - (cruiser(sim.current_time()) + vec2(-200.0, 0.0))).length() < 2.0, "{}", ship.position);
+    assert!((ship.velocity - vec2(0.0, 40.0)).length() < 0.5);
+
+    let mut io = MockIo::new(Class::Fighter);
+    io.velocity = vec2(0.0, 39.9);
+    assert!((navigation::match_velocity(&io, vec2(0.0, 40.0)) - vec2(0.0, 6.0)).length() < 1e-9); // no overshoot within the tick
+}
+
+#[test]
+fn paths_pass_every_waypoint_in_order() {
+    let waypoints = vec![vec2(1000.0, 0.0), vec2(1000.0, 1000.0), vec2(0.0, 1000.0)];
+    let mut sim = Simulation::new(1);
+    let mut path = Path::new(waypoints.clone(), 50.0);
+    let id = sim.add_ship_with_pilot(Class::Fighter, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), 0.0, Pilot::Scripted(Box::new(move || accelerate(path.follow(&OortIo)))));
+
+    let mut passed = Vec::new();
+    while sim.current_time() < 60.0 {
+        sim.tick();
+        let position = sim.ship(id).unwrap().position;
+        if let Some(i) = waypoints.iter().position(|waypoint| (*waypoint - position).length() < 50.0) {
+            if passed.last() != Some(&i) {
+                passed.push(i);
+            }
+        }
+    }
+    assert_eq!(passed, vec![0, 1, 2]);
+    let ship = sim.ship(id).unwrap();
+    assert!((ship.position - waypoints[2]).length() < 1.0);
+    assert!(ship.velocity.length() < 0.5);
+}
+
+#[test]
+fn dogfight_acceleration_holds_across_the_heading_wrap() {
+    // a fighter pointed just short of a full turn, its target just past it: the same geometry as a heading of -0.1
+    let settings = Settings::new();
+    let target = vec2(2000.0, 0.0).rotate(0.1);
+    let mut io = MockIo::new(Class::Fighter);
+    let mut accelerations = Vec::new();
+    for heading in [TAU - 0.1, -0.1] {
+        io.heading = heading;
+        let angular_speed = navigation::get_angular_speed(&io, target, vec2(0.0, 100.0));
+        accelerations.push(navigation::calculate_dogfight_acceleration(&io, &settings, target, angular_speed));
+    }
+    assert!((accelerations[0] - accelerations[1]).length() < 1e-9, "{} vs {}", accelerations[0], accelerations[1]);
+
+    // and it is the most the thrusters give, pointed where the dogfight mode wants it
+    let body = accelerations[0].rotate(-io.heading);
+    assert!(body.x > 0.0 && body.x <= io.max_forward_acceleration + 1e-9 && body.y.abs() <= io.max_lateral_acceleration + 1e-9);
+    assert!((body.x - io.max_forward_acceleration).abs() < 1e-9 || (body.y.abs() - io.max_lateral_acceleration).abs() < 1e-9);
+}
+
+#[test]
+fn fighters_still_win_the_dogfight_from_a_wrapped_heading() {
+    let mut sim = Simulation::new(4);
+    sim.add_ship(Class::Fighter, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), TAU - 0.2);
+    let target = sim.add_ship(Class::Target, 1, vec2(1500.0, 300.0), vec2(0.0, -80.0), 0.0);
+    assert_eq!(sim.run(60 * 30), Some(0));
+    assert!(!sim.ship(target).unwrap().alive());
+}
@@ -0,0 +1,35 @@
+use oort_api::prelude::*;
+
+use fleet_v1::mock::MockIo;
+use fleet_v1::{fuse, point_defense};
+use fleet_v1::radar::TrackTable;
+use fleet_v1::settings::Settings;
+
+mod common;
+
+#[test]
+fn closest_approach_of_a_crossing_missile() {
+    let io = MockIo::new(Class::Frigate);
+    let mut table = TrackTable::new(&Settings::new());
+    let id = table.ingest(&io, &common::contact(Class::Missile, vec2(1000.0, 100.0), vec2(-500.0, 0.0)));
+
+    let radar_track = table.get(id).unwrap();
+    let approach = fuse::closest_approach(&io, radar_track.position(&io), radar_track.velocity(&io), radar_track.track.acceleration());
+    assert!((approach.time - 2.0).abs() < 1e-6);
+    assert!((approach.miss_distance - 100.0).abs() < 1e-6);
+}
+
+#[test]
+fn only_incoming_munitions_are_threats() {
+    let settings = Settings::new();
+    let io = MockIo::new(Class::Frigate);
+    let mut table = TrackTable::new(&settings);
+    let late = table.ingest(&io, &common::contact(Class::Torpedo, vec2(0.0, -2500.0), vec2(0.0, 500.0)));
+    let soon = table.ingest(&io, &common::contact(Class::Missile, vec2(1000.0, 0.0), vec2(-800.0, 0.0)));
+    table.ingest(&io, &common::contact(Class::Missile, vec2(-1000.0, 0.0), vec2(-800.0, 0.0))); // flying away
+    table.ingest(&io, &common::contact(Class::Missile, vec2(1000.0, 2000.0), vec2(-800.0, 0.0))); // passing wide
+    table.ingest(&io, &common::contact(Class::Fighter, vec2(500.0, 0.0), vec2(-800.0, 0.0))); // not a munition
+
+    let threats: Vec<u32> = point_defense::threats(&io, &settings, &table).iter().map(|radar_track| radar_track.id).collect();
+    assert_eq!(threats, vec![soon, late]);
+}
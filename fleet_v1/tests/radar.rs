@@ -0,0 +1,71 @@
+use oort_api::prelude::*;
+
+use fleet_v1::io::ShipIo;
+use fleet_v1::mock::MockIo;
+use fleet_v1::radar::{Radar, TrackTable};
+use fleet_v1::settings::Settings;
+
+mod common;
+
+#[test]
+fn contacts_are_associated_to_their_own_tracks() {
+    let mut io = MockIo::new(Class::Fighter);
+    let mut table = TrackTable::new(&Settings::new());
+    let a = table.ingest(&io, &common::contact(Class::Fighter, vec2(1000.0, 0.0), vec2(0.0, 100.0)));
+    let b = table.ingest(&io, &common::contact(Class::Fighter, vec2(-2000.0, 500.0), vec2(0.0, 0.0)));
+    assert_ne!(a, b);
+
+    // a few frames later each target shows up where its track predicted it
+    io.current_tick = 30;
+    assert_eq!(table.ingest(&io, &common::contact(Class::Fighter, vec2(1000.0, 50.0), vec2(0.0, 100.0))), a);
+    assert_eq!(table.ingest(&io, &common::contact(Class::Fighter, vec2(-2000.0, 500.0), vec2(0.0, 0.0))), b);
+    assert_eq!(table.tracks().count(), 2);
+    assert!((table.get(a).unwrap().position(&io) - vec2(1000.0, 50.0)).length() < 1.0);
+}
+
+#[test]
+fn different_classes_never_share_a_track() {
+    let io = MockIo::new(Class::Fighter);
+    let mut table = TrackTable::new(&Settings::new());
+    let ship = table.ingest(&io, &common::contact(Class::Fighter, vec2(1000.0, 0.0), vec2(0.0, 0.0)));
+    let missile = table.ingest(&io, &common::contact(Class::Missile, vec2(1000.0, 5.0), vec2(0.0, 0.0)));
+    assert_ne!(ship, missile);
+    // munitions are tracked, but are not enemy ships
+    assert_eq!(table.enemies().map(|radar_track| radar_track.id).collect::<Vec<_>>(), vec![ship]);
+}
+
+#[test]
+fn tracks_without_returns_are_dropped() {
+    let settings = Settings::new();
+    let mut io = MockIo::new(Class::Fighter);
+    let mut table = TrackTable::new(&settings);
+    let id = table.ingest(&io, &common::contact(Class::Cruiser, vec2(1000.0, 0.0), vec2(0.0, 0.0)));
+
+    io.current_tick = (settings.track_drop_time / TICK_LENGTH) as u32;
+    table.drop_stale(&io);
+    assert!(table.get(id).is_some());
+
+    io.current_tick += 1;
+    table.drop_stale(&io);
+    assert!(table.get(id).is_none());
+}
+
+#[test]
+fn radar_searches_between_looks_at_the_target() {
+    let settings = Settings::new();
+    let mut io = MockIo::new(Class::Fighter);
+    let mut radar = Radar::new(&settings);
+    io.contact = Some(common::contact(Class::Fighter, vec2(0.0, 3000.0), vec2(0.0, 0.0)));
+    radar.update(&io);
+    let target = radar.table.enemies().next().unwrap().id;
+
+    let mut searched = false;
+    for _ in 0..settings.tws_search_interval {
+        radar.steer(&mut io, Some(target), None);
+        searched |= io.radar_width >= TAU / settings.scan_sections as f64;
+        if !searched {
+            assert!(angle_diff(io.radar_heading(), PI / 2.0).abs() < 1e-6);
+        }
+    }
+    assert!(searched);
+}
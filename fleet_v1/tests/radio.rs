@@ -0,0 +1,91 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use oort_api::prelude::*;
+
+use fleet_v1::radio::{self, DataLink, DecodeError, Packet, RadioMessage, Status, TargetAssignment, TargetReport};
+use fleet_v1::sim::{Pilot, Simulation};
+
+fn messages() -> Vec<RadioMessage> {
+    vec![
+        RadioMessage::TargetReport(TargetReport { class: Class::Frigate, position: vec2(12000.5, -300.25), velocity: vec2(-80.0, 12.5), tick: 4711 }),
+        RadioMessage::TargetAssignment(TargetAssignment { assignee: 3, class: Class::Fighter, position: vec2(-500.0, 250.0), velocity: vec2(100.0, 0.0) }),
+        RadioMessage::Status(Status { class: Class::Cruiser, health: 17500.0, position: vec2(0.0, -20000.0), velocity: vec2(0.0, 0.0) }),
+    ]
+}
+
+#[test]
+fn every_message_survives_the_round_trip() {
+    for message in messages() {
+        let packet = Packet { sender: 42, message };
+        assert_eq!(radio::decode(&radio::encode(&packet)), Ok(packet));
+    }
+}
+
+#[test]
+fn corrupted_messages_are_rejected() {
+    let packet = Packet { sender: 42, message: messages()[0] };
+    assert_eq!(radio::decode(&[0; radio::PAYLOAD_SIZE]), Err(DecodeError::Empty));
+
+    let mut flipped = radio::encode(&packet);
+    flipped[7] ^= 0x10;
+    assert_eq!(radio::decode(&flipped), Err(DecodeError::BadChecksum));
+
+    // raw f64 messages from other code don't pass for ours
+    let mut foreign = [0u8; radio::PAYLOAD_SIZE];
+    for (chunk, value) in foreign.chunks_mut(8).zip([1.0f64, 2.0, 3.0, 4.0]) {
+        chunk.copy_from_slice(&value.to_ne_bytes());
+    }
+    assert!(radio::decode(&foreign).is_err());
+}
+
+#[test]
+fn ships_exchange_as_many_messages_per_tick_as_they_have_radios() {
+    let received = Rc::new(RefCell::new(Vec::new()));
+    let fighter_received = Rc::new(RefCell::new(Vec::new()));
+
+    let mut sim = Simulation::new(8);
+    let mut sender_link = DataLink::new(Class::Cruiser);
+    let sender = sim.add_ship_with_pilot(Class::Cruiser, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), 0.0, Pilot::Scripted(Box::new(move || {
+        for message in messages() {
+            sender_link.send(message);
+        }
+        sender_link.flush();
+    })));
+    let mut receiver_link = DataLink::new(Class::Cruiser);
+    let log = received.clone();
+    sim.add_ship_with_pilot(Class::Cruiser, 0, vec2(1000.0, 0.0), vec2(0.0, 0.0), 0.0, Pilot::Scripted(Box::new(move || {
+        log.borrow_mut().push(receiver_link.receive());
+        receiver_link.flush();
+    })));
+    // a fighter has a single radio
+    let mut fighter_link = DataLink::new(Class::Fighter);
+    let fighter_log = fighter_received.clone();
+    sim.add_ship_with_pilot(Class::Fighter, 0, vec2(0.0, 1000.0), vec2(0.0, 0.0), 0.0, Pilot::Scripted(Box::new(move || {
+        fighter_log.borrow_mut().push(fighter_link.receive());
+        fighter_link.flush();
+    })));
+    // other teams don't hear us
+    let eavesdropped = Rc::new(RefCell::new(0));
+    let mut enemy_link = DataLink::new(Class::Cruiser);
+    let eavesdrop_log = eavesdropped.clone();
+    sim.add_ship_with_pilot(Class::Cruiser, 1, vec2(-1000.0, 0.0), vec2(0.0, 0.0), 0.0, Pilot::Scripted(Box::new(move || {
+        *eavesdrop_log.borrow_mut() += enemy_link.receive().len();
+        enemy_link.flush();
+    })));
+
+    for _ in 0..3 * 8 {
+        sim.tick();
+    }
+    // channels are set on the first tick and take effect on the next
+    let last = received.borrow().last().unwrap().clone();
+    assert_eq!(last.len(), messages().len());
+    assert!(last.iter().all(|packet| packet.sender == sender));
+    for message in messages() {
+        assert!(last.iter().any(|packet| packet.message == message));
+    }
+    // one message at most, but over a few ticks the sender comes by the fighter's channel
+    assert!(fighter_received.borrow().iter().all(|packets| packets.len() <= 1));
+    assert!(fighter_received.borrow().iter().any(|packets| packets.len() == 1 && packets[0].sender == sender));
+    assert_eq!(*eavesdropped.borrow(), 0);
+}
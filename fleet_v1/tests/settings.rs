@@ -0,0 +1,38 @@
+use oort_api::prelude::*;
+
+use fleet_v1::settings::{ClassSettings, Settings};
+use fleet_v1::sim::Simulation;
+
+#[test]
+fn classes_without_an_override_fly_the_defaults() {
+    let mut settings = ClassSettings::new();
+    let fighter = Settings { n_factor: 5.0, ..Settings::new() };
+    settings.set(Class::Fighter, fighter);
+    settings.set(Class::Fighter, Settings { n_factor: 3.0, ..fighter });
+
+    assert_eq!(settings.get(Class::Fighter).n_factor, 3.0);
+    assert_eq!(settings.get(Class::Cruiser), Settings::new());
+    assert_eq!(settings.overrides.len(), 1);
+}
+
+#[test]
+fn team_settings_reach_the_ships() {
+    // the same missile raid, once against a cruiser that has its point defense tuned out of range
+    let cruiser_damage = |settings: ClassSettings| {
+        let mut sim = Simulation::new(6);
+        sim.set_team_settings(0, settings);
+        let cruiser = sim.add_ship(Class::Cruiser, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), 0.0);
+        for y in [-300.0, 300.0] {
+            sim.add_ship(Class::Missile, 1, vec2(2500.0, y), vec2(-200.0, 0.0), PI);
+        }
+        for _ in 0..60 * 10 {
+            sim.tick();
+        }
+        sim.ship(cruiser).unwrap().damage_taken
+    };
+    let mut no_point_defense = ClassSettings::new();
+    no_point_defense.set(Class::Cruiser, Settings { pd_range: 0.0, ..Settings::new() });
+
+    assert_eq!(cruiser_damage(ClassSettings::new()), 0.0);
+    assert!(cruiser_damage(no_point_defense) > 0.0);
+}
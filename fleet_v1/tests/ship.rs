@@ -0,0 +1,63 @@
+use oort_api::prelude::*;
+
+use fleet_v1::loadout::{self, Weapon};
+use fleet_v1::settings::ClassSettings;
+use fleet_v1::ship::{Registry, Ship};
+use fleet_v1::sim::{Pilot, Simulation};
+
+mod common;
+
+fn pilot(registry: Registry) -> Pilot { // fleet_v1 with a custom registry, created on the first tick like the real thing
+    let mut ship = None;
+    Pilot::Scripted(Box::new(move || ship.get_or_insert_with(|| Ship::with_registry(&registry, &ClassSettings::new())).tick()))
+}
+
+#[test]
+fn every_fleet_class_is_registered() {
+    let registry = Registry::new();
+    for class in [Class::Fighter, Class::Frigate, Class::Cruiser, Class::Missile, Class::Torpedo] {
+        assert!(registry.get(class).is_some(), "{:?}", class);
+    }
+    assert!(registry.get(Class::Target).is_none());
+}
+
+#[test]
+fn registering_replaces_the_controller() {
+    let mut registry = Registry::new();
+    let count = registry.controllers.len();
+    registry.register(Class::Fighter, registry.get(Class::Cruiser).unwrap());
+    registry.register(Class::Target, registry.get(Class::Cruiser).unwrap());
+    assert_eq!(registry.controllers.len(), count + 1);
+}
+
+#[test]
+fn unhandled_classes_find_their_weapons_and_fire() {
+    // nothing in the loadout table for a target, fit it a turret in a slot the fallback has to find for itself
+    let mut sim = Simulation::new(1);
+    let target = sim.add_ship_with_pilot(Class::Target, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), 0.0, Pilot::Fleet(None));
+    sim.ship_mut(target).unwrap().weapons = vec![Weapon { slot: 2, ..loadout::loadout(Class::Frigate)[1] }];
+    let fighter = sim.add_ship_with_pilot(Class::Fighter, 1, vec2(1500.0, 300.0), vec2(0.0, 0.0), PI, common::inert());
+    for _ in 0..60 * 10 {
+        sim.tick();
+    }
+    assert!(sim.ship(target).unwrap().alive());
+    assert!(sim.ship(fighter).unwrap().damage_taken > 0.0);
+
+    // without anything fitted there is nothing to find
+    let mut sim = Simulation::new(1);
+    sim.add_ship_with_pilot(Class::Target, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), 0.0, Pilot::Fleet(None));
+    let fighter = sim.add_ship_with_pilot(Class::Fighter, 1, vec2(1500.0, 300.0), vec2(0.0, 0.0), PI, common::inert());
+    for _ in 0..60 * 10 {
+        sim.tick();
+    }
+    assert_eq!(sim.ship(fighter).unwrap().damage_taken, 0.0);
+}
+
+#[test]
+fn the_fallback_fights_with_what_the_ship_has() {
+    let mut sim = Simulation::new(2);
+    sim.add_ship_with_pilot(Class::Fighter, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), 0.0, pilot(Registry { controllers: Vec::new() }));
+    let target = sim.add_ship(Class::Target, 1, vec2(2000.0, 500.0), vec2(0.0, 0.0), 0.0);
+    assert_eq!(sim.run(60 * 60), Some(0));
+    assert!(!sim.ship(target).unwrap().alive());
+}
@@ -0,0 +1,143 @@
+use oort_api::prelude::*;
+
+use fleet_v1::sim::{Pilot, Simulation};
+
+mod common;
+
+#[test]
+fn fighter_destroys_stationary_target() {
+    let mut sim = Simulation::new(1);
+    sim.add_ship(Class::Fighter, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), 0.0);
+    let target = sim.add_ship(Class::Target, 1, vec2(3000.0, 500.0), vec2(0.0, 0.0), 0.0);
+
+    assert_eq!(sim.run(60 * 60), Some(0));
+    assert!(!sim.ship(target).unwrap().alive());
+}
+
+#[test]
+fn fighter_launches_missiles_at_distant_target() {
+    let mut sim = Simulation::new(2);
+    sim.add_ship(Class::Fighter, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), 0.0);
+    sim.add_ship(Class::Target, 1, vec2(5000.0, 0.0), vec2(0.0, 0.0), 0.0);
+
+    for _ in 0..60 {
+        sim.tick();
+    }
+    assert!(sim.ships.iter().any(|ship| ship.class == Class::Missile && ship.team == 0));
+}
+
+#[test]
+fn scripted_pilot_drives_the_ship() {
+    let mut sim = Simulation::new(3);
+    let id = sim.add_ship_with_pilot(Class::Fighter, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), 0.0, Pilot::Scripted(Box::new(|| {
+        accelerate(vec2(0.0, 100.0));
+        torque(100.0);
+    })));
+
+    for _ in 0..60 {
+        sim.tick();
+    }
+    let ship = sim.ship(id).unwrap();
+    // torque is clamped to the fighter's TAU rad/s²
+    assert!((ship.angular_velocity - TAU).abs() < 1e-9);
+    // lateral acceleration is clamped to 30 m/s² in the ship frame
+    assert!(ship.velocity.y > 30.0);
+    assert!(ship.velocity.x.abs() < 1e-9);
+}
+
+#[test]
+fn same_seed_same_engagement() {
+    let run = |seed| {
+        let mut sim = Simulation::new(seed);
+        sim.add_ship(Class::Fighter, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), 0.0);
+        sim.add_ship(Class::Target, 1, vec2(2000.0, -800.0), vec2(0.0, 0.0), 0.0);
+        for _ in 0..300 {
+            sim.tick();
+        }
+        let ship = &sim.ships[0];
+        (ship.position.x, ship.position.y, ship.heading)
+    };
+    assert_eq!(run(7), run(7));
+}
+
+#[test]
+fn fighter_engages_a_moving_target() {
+    let mut sim = Simulation::new(4);
+    sim.add_ship(Class::Fighter, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), 0.0);
+    let target = sim.add_ship(Class::Target, 1, vec2(2000.0, -800.0), vec2(0.0, 50.0), 0.0);
+
+    assert_eq!(sim.run(60 * 60), Some(0));
+    assert!(!sim.ship(target).unwrap().alive());
+}
+
+#[test]
+fn fighter_destroys_several_targets() {
+    let mut sim = Simulation::new(5);
+    sim.add_ship(Class::Fighter, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), 0.0);
+    sim.add_ship(Class::Target, 1, vec2(2500.0, 600.0), vec2(0.0, 0.0), 0.0);
+    sim.add_ship(Class::Target, 1, vec2(-1500.0, -2000.0), vec2(0.0, 0.0), 0.0);
+
+    assert_eq!(sim.run(60 * 120), Some(0));
+}
+
+#[test]
+fn cruiser_shoots_down_incoming_missiles() {
+    let mut sim = Simulation::new(6);
+    let cruiser = sim.add_ship(Class::Cruiser, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), 0.0);
+    for y in [-300.0, 300.0] {
+        sim.add_ship(Class::Missile, 1, vec2(2500.0, y), vec2(-200.0, 0.0), PI);
+    }
+
+    for _ in 0..60 * 10 {
+        sim.tick();
+    }
+    assert_eq!(sim.ships.iter().filter(|ship| ship.class == Class::Missile && ship.team == 1 && ship.alive()).count(), 0);
+    assert_eq!(sim.ship(cruiser).unwrap().damage_taken, 0.0);
+}
+
+#[test]
+fn fighter_evades_a_missile() {
+    let mut sim = Simulation::new(7);
+    let fighter = sim.add_ship(Class::Fighter, 0, vec2(0.0, 0.0), vec2(0.0, 100.0), 0.0);
+    sim.add_ship(Class::Missile, 1, vec2(2500.0, 0.0), vec2(-300.0, 0.0), PI);
+
+    for _ in 0..60 * 20 {
+        sim.tick();
+    }
+    assert!(sim.ship(fighter).unwrap().alive());
+}
+
+#[test]
+fn frigate_stands_off_and_fights_with_its_railgun_and_turrets() {
+    let mut sim = Simulation::new(8);
+    let frigate = sim.add_ship(Class::Frigate, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), 0.0);
+    let cruiser = sim.add_ship_with_pilot(Class::Cruiser, 1, vec2(2000.0, 500.0), vec2(0.0, 20.0), PI, common::inert());
+    let distance = |sim: &Simulation| (sim.ship(frigate).unwrap().position - sim.ship(cruiser).unwrap().position).length();
+    let start = distance(&sim);
+
+    for _ in 0..60 * 15 {
+        sim.tick();
+    }
+    // a fighter would have closed in, the frigate backs off to where its turrets still reach
+    assert!(distance(&sim) > start + 300.0, "{}", distance(&sim));
+    assert!(sim.ship(cruiser).unwrap().damage_taken > 0.0);
+    // the hull never masked the broadside turrets for long
+    let hits = sim.ship(frigate).unwrap().hits;
+    assert!(hits[1] > 0 && hits[2] > 0, "{:?}", hits);
+}
+
+#[test]
+fn cruiser_opens_up_to_its_band() {
+    let mut sim = Simulation::new(9);
+    let cruiser = sim.add_ship(Class::Cruiser, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), 0.0);
+    let frigate = sim.add_ship_with_pilot(Class::Frigate, 1, vec2(1000.0, 0.0), vec2(0.0, 0.0), PI, common::inert());
+    let distance = |sim: &Simulation| (sim.ship(cruiser).unwrap().position - sim.ship(frigate).unwrap().position).length();
+
+    // no dogfighting at 1 km, the cruiser backs away while its flak works
+    while sim.current_time() < 30.0 && sim.ship(frigate).unwrap().alive() {
+        sim.tick();
+        assert!(distance(&sim) >= 1000.0 - 1e-6, "{}", distance(&sim));
+    }
+    assert!(distance(&sim) > 1100.0, "{}", distance(&sim));
+    assert!(!sim.ship(frigate).unwrap().alive());
+}
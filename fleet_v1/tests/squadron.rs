@@ -0,0 +1,90 @@
+use oort_api::prelude::*;
+
+use fleet_v1::fleet::FleetPicture;
+use fleet_v1::mock::MockIo;
+use fleet_v1::radar::TrackTable;
+use fleet_v1::radio::{DataLink, Packet, RadioMessage, TargetAssignment};
+use fleet_v1::settings::Settings;
+use fleet_v1::sim::Simulation;
+use fleet_v1::squadron::Squadron;
+
+mod common;
+
+#[test]
+fn leader_spreads_the_squadron_over_the_enemies() {
+    let settings = Settings::new();
+    let mut io = MockIo::new(Class::Fighter);
+    io.id = 1;
+    let mut fleet = FleetPicture::new(&settings);
+    let mut table = TrackTable::new(&settings);
+    fleet.update(&io, &[common::heartbeat(2, Class::Fighter, vec2(0.0, 100.0)), common::heartbeat(3, Class::Fighter, vec2(0.0, -100.0)), common::heartbeat(4, Class::Fighter, vec2(0.0, 200.0))], &mut table);
+    let north = table.ingest(&io, &common::enemy(vec2(0.0, 3000.0)));
+    let south = table.ingest(&io, &common::enemy(vec2(0.0, -3000.0)));
+
+    let mut squadron = Squadron::new(&settings);
+    let mut link = DataLink::new(Class::Fighter);
+    assert!(squadron.is_leader(&io, &fleet));
+    squadron.lead(&io, &fleet, &table, &mut link);
+
+    let assignments: Vec<TargetAssignment> = link.outbox().iter().filter_map(|message| match message {
+        RadioMessage::TargetAssignment(assignment) => Some(*assignment),
+        _ => None,
+    }).collect();
+    assert_eq!(assignments.len(), 3);
+    let north_count = assignments.iter().filter(|assignment| assignment.position.y > 0.0).count()
+        + usize::from(squadron.assigned_target() == Some(north));
+    assert_eq!(north_count, 2);
+    assert!(squadron.assigned_target() == Some(north) || squadron.assigned_target() == Some(south));
+    // the nearest fighters go north
+    assert!(assignments.iter().any(|assignment| assignment.assignee == 4 && assignment.position.y > 0.0));
+    assert!(assignments.iter().any(|assignment| assignment.assignee == 3 && assignment.position.y < 0.0));
+}
+
+#[test]
+fn wingmen_follow_their_assignment_until_it_goes_stale() {
+    let settings = Settings::new();
+    let mut io = MockIo::new(Class::Fighter);
+    io.id = 5;
+    let mut fleet = FleetPicture::new(&settings);
+    let mut table = TrackTable::new(&settings);
+    let mut squadron = Squadron::new(&settings);
+    let target = table.ingest(&io, &common::enemy(vec2(2000.0, 0.0)));
+    let other = table.ingest(&io, &common::enemy(vec2(-2000.0, 0.0)));
+
+    let assignment = |assignee, position| Packet { sender: 1, message: RadioMessage::TargetAssignment(TargetAssignment { assignee, class: Class::Fighter, position, velocity: vec2(0.0, 0.0) }) };
+    let packets = [common::heartbeat(1, Class::Fighter, vec2(0.0, 0.0)), assignment(5, vec2(2000.0, 3.0)), assignment(6, vec2(-2000.0, 0.0))];
+    fleet.update(&io, &packets, &mut table);
+    squadron.update(&io, &packets, &fleet, &mut table);
+
+    assert!(!squadron.is_leader(&io, &fleet));
+    assert_eq!(squadron.assigned_target(), Some(target));
+    assert_eq!(squadron.engaged(), vec![other]);
+    let mut link = DataLink::new(Class::Fighter);
+    squadron.lead(&io, &fleet, &table, &mut link);
+    assert!(link.outbox().is_empty());
+
+    // the leader goes quiet: we are on our own again
+    io.current_tick = (settings.assignment_timeout / TICK_LENGTH) as u32 + 1;
+    table.ingest(&io, &common::enemy(vec2(2000.0, 0.0)));
+    table.ingest(&io, &common::enemy(vec2(-2000.0, 0.0)));
+    squadron.update(&io, &[], &fleet, &mut table);
+    assert_eq!(squadron.assigned_target(), None);
+    assert!(squadron.engaged().is_empty());
+}
+
+#[test]
+fn squadron_splits_up_instead_of_dogpiling() {
+    let mut sim = Simulation::new(10);
+    let fighters: Vec<u32> = (0..4).map(|i| sim.add_ship(Class::Fighter, 0, vec2(0.0, 100.0 + 100.0 * i as f64), vec2(0.0, 0.0), 0.0)).collect();
+    sim.add_ship(Class::Fighter, 1, vec2(3000.0, 2500.0), vec2(0.0, 0.0), 0.0);
+    sim.add_ship(Class::Fighter, 1, vec2(3000.0, -2500.0), vec2(0.0, 0.0), 0.0);
+
+    // look before the fight is over, the survivors all turn to whatever is left
+    for _ in 0..60 * 4 {
+        sim.tick();
+    }
+    let heading_south = fighters.iter().filter(|id| sim.ship(**id).unwrap().velocity.y < -20.0).count();
+    let heading_north = fighters.iter().filter(|id| sim.ship(**id).unwrap().velocity.y > 20.0).count();
+    assert!(heading_south >= 1, "{heading_north} north, {heading_south} south");
+    assert!(heading_north >= 1, "{heading_north} north, {heading_south} south");
+}
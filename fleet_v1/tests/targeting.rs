@@ -0,0 +1,68 @@
+use oort_api::prelude::*;
+
+use fleet_v1::mock::MockIo;
+use fleet_v1::radar::TrackTable;
+use fleet_v1::settings::Settings;
+use fleet_v1::targeting::{self, TargetSelector, WeaponProfile};
+
+mod common;
+
+const GUN: WeaponProfile = WeaponProfile { bullet_speed: 1000.0, damage_per_second: 300.0 };
+
+#[test]
+fn munitions_are_never_selected() {
+    let settings = Settings::new();
+    let io = MockIo::new(Class::Fighter);
+    let mut table = TrackTable::new(&settings);
+    table.ingest(&io, &common::contact(Class::Missile, vec2(500.0, 0.0), vec2(-300.0, 0.0)));
+    table.ingest(&io, &common::contact(Class::Torpedo, vec2(800.0, 0.0), vec2(-300.0, 0.0)));
+    assert_eq!(TargetSelector::new(GUN, &settings).select(&io, &table, &[]), None);
+}
+
+#[test]
+fn closing_and_near_targets_score_higher() {
+    let settings = Settings::new();
+    let io = MockIo::new(Class::Fighter);
+    let mut table = TrackTable::new(&settings);
+    let near = table.ingest(&io, &common::contact(Class::Fighter, vec2(1000.0, 0.0), vec2(0.0, 0.0)));
+    let far = table.ingest(&io, &common::contact(Class::Fighter, vec2(0.0, 5000.0), vec2(0.0, 0.0)));
+    let closing = table.ingest(&io, &common::contact(Class::Fighter, vec2(-1000.0, 0.0), vec2(300.0, 0.0)));
+
+    let score = |id| targeting::score(&io, &settings, table.get(id).unwrap(), &GUN, false);
+    assert!(score(near) > score(far));
+    assert!(score(closing) > score(near));
+    assert!(targeting::score(&io, &settings, table.get(near).unwrap(), &GUN, true) < score(near));
+}
+
+#[test]
+fn engaged_targets_are_left_to_their_shooter() {
+    let settings = Settings::new();
+    let io = MockIo::new(Class::Fighter);
+    let mut table = TrackTable::new(&settings);
+    let a = table.ingest(&io, &common::contact(Class::Fighter, vec2(1000.0, 0.0), vec2(0.0, 0.0)));
+    let b = table.ingest(&io, &common::contact(Class::Fighter, vec2(0.0, 1200.0), vec2(0.0, 0.0)));
+
+    assert_eq!(TargetSelector::new(GUN, &settings).select(&io, &table, &[]), Some(a));
+    assert_eq!(TargetSelector::new(GUN, &settings).select(&io, &table, &[a]), Some(b));
+}
+
+#[test]
+fn selection_sticks_until_a_clearly_better_target_shows_up() {
+    let settings = Settings::new();
+    let mut io = MockIo::new(Class::Fighter);
+    let mut table = TrackTable::new(&settings);
+    let mut selector = TargetSelector::new(GUN, &settings);
+    let first = table.ingest(&io, &common::contact(Class::Fighter, vec2(1000.0, 0.0), vec2(0.0, 0.0)));
+    assert_eq!(selector.select(&io, &table, &[]), Some(first));
+
+    // slightly better: keep shooting the first one
+    let similar = table.ingest(&io, &common::contact(Class::Fighter, vec2(0.0, 900.0), vec2(0.0, 0.0)));
+    assert_eq!(selector.select(&io, &table, &[]), Some(first));
+
+    // a lot better: switch
+    io.current_tick = 1;
+    let close = table.ingest(&io, &common::contact(Class::Fighter, vec2(-100.0, 0.0), vec2(0.0, 0.0)));
+    assert_ne!(close, similar);
+    assert_eq!(selector.select(&io, &table, &[]), Some(close));
+    assert_eq!(selector.current(), Some(close));
+}
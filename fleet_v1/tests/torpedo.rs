@@ -0,0 +1,44 @@
+use oort_api::prelude::*;
+
+use fleet_v1::sim::Simulation;
+
+mod common;
+
+#[test]
+fn torpedoes_pass_fighters_for_capital_ships() {
+    for class in [Class::Frigate, Class::Cruiser] {
+        let mut sim = Simulation::new(1);
+        let torpedo = sim.add_ship(Class::Torpedo, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), 0.0);
+        // the fighter sits right on the nose, the capital ship off to the side
+        let fighter = sim.add_ship_with_pilot(Class::Fighter, 1, vec2(3000.0, 0.0), vec2(0.0, 0.0), PI, common::inert());
+        let capital = sim.add_ship_with_pilot(class, 1, vec2(4000.0, 1500.0), vec2(0.0, 30.0), PI, common::inert());
+        while sim.current_time() < 30.0 && sim.ship(torpedo).unwrap().alive() {
+            sim.tick();
+        }
+        assert_eq!(sim.ship(fighter).unwrap().damage_taken, 0.0, "{:?}", class);
+        assert!(sim.ship(capital).unwrap().damage_taken > 0.0, "{:?}", class);
+    }
+}
+
+#[test]
+fn torpedoes_take_fighters_when_nothing_bigger_is_around() {
+    let mut sim = Simulation::new(2);
+    sim.add_ship(Class::Torpedo, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), 0.0);
+    let fighter = sim.add_ship_with_pilot(Class::Fighter, 1, vec2(3000.0, 1000.0), vec2(0.0, 50.0), PI, common::inert());
+    while sim.current_time() < 30.0 && sim.ship(fighter).unwrap().alive() {
+        sim.tick();
+    }
+    assert!(!sim.ship(fighter).unwrap().alive());
+}
+
+#[test]
+fn searching_torpedoes_hold_the_launch_heading() {
+    let mut sim = Simulation::new(3);
+    let torpedo = sim.add_ship(Class::Torpedo, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), -2.0);
+    for _ in 0..120 { // nothing in range to find
+        sim.tick();
+        let ship = sim.ship(torpedo).unwrap();
+        assert!(angle_diff(ship.heading, -2.0).abs() < 1e-3, "heading {} at tick {}", ship.heading, sim.current_tick());
+        assert!(ship.angular_velocity.abs() < 1e-3);
+    }
+}
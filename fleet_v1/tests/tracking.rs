@@ -0,0 +1,74 @@
+use oort_api::prelude::*;
+use oort_api::prelude::maths_rs::num::Base;
+use oort_api::prelude::oorandom::Rand64;
+
+use fleet_v1::mock::MockIo;
+use fleet_v1::settings::Settings;
+use fleet_v1::tracking::Track;
+
+fn noisy_contact(rng: &mut Rand64, position: Vec2, velocity: Vec2, position_noise: f64, velocity_noise: f64, snr: f64) -> ScanResult {
+    let mut noise = || vec2(rng.rand_float() - 0.5, rng.rand_float() - 0.5) * 2.0;
+    ScanResult { class: Class::Fighter, position: position + noise() * position_noise, velocity: velocity + noise() * velocity_noise, rssi: 0.0, snr }
+}
+
+#[test]
+fn filtered_acceleration_beats_finite_difference() {
+    let mut rng = Rand64::new(5);
+    let mut io = MockIo::new(Class::Fighter);
+    let settings = Settings::new();
+    let mut track = Track::new();
+    let acceleration = vec2(30.0, -45.0);
+    let (mut position, mut velocity) = (vec2(3000.0, 1000.0), vec2(-200.0, 100.0));
+    let mut last_measured_velocity = velocity;
+    let (mut filtered_error, mut differenced_error) = (0.0, 0.0);
+
+    for tick in 0..600 {
+        io.current_tick = tick;
+        let contact = noisy_contact(&mut rng, position, velocity, 5.0, 0.5, 20.0);
+        track.update(&io, &settings, &contact);
+        if tick >= 300 {
+            filtered_error += (track.acceleration() - acceleration).length();
+            differenced_error += ((contact.velocity - last_measured_velocity) * 60.0 - acceleration).length();
+        }
+        last_measured_velocity = contact.velocity;
+        velocity += acceleration * TICK_LENGTH;
+        position += velocity * TICK_LENGTH;
+    }
+    assert!(filtered_error / 300.0 < 10.0, "mean filtered error {}", filtered_error / 300.0);
+    assert!(filtered_error * 3.0 < differenced_error);
+}
+
+#[test]
+fn skipped_frames_are_predicted_over() {
+    let mut io = MockIo::new(Class::Fighter);
+    let settings = Settings::new();
+    let mut track = Track::new();
+    let velocity = vec2(100.0, 50.0);
+    let contact = |tick: u32| ScanResult { class: Class::Fighter, position: velocity * (tick as f64 * TICK_LENGTH), velocity, rssi: 0.0, snr: 30.0 };
+
+    for tick in 0..60 {
+        io.current_tick = tick;
+        track.update(&io, &settings, &contact(tick));
+    }
+    io.current_tick = 80;
+    track.update(&io, &settings, &contact(80));
+    assert!((track.position() - contact(80).position).length() < 0.5);
+    assert!(track.acceleration().length() < 1.0);
+}
+
+#[test]
+fn a_jumping_contact_starts_a_new_track() {
+    let mut io = MockIo::new(Class::Fighter);
+    let settings = Settings::new();
+    let mut track = Track::new();
+    assert_eq!(track.last_update_tick(), None);
+    for tick in 0..30 {
+        io.current_tick = tick;
+        track.update(&io, &settings, &ScanResult { class: Class::Fighter, position: vec2(1000.0, 0.0), velocity: Vec2::zero(), rssi: 0.0, snr: 30.0 });
+    }
+    io.current_tick = 30;
+    track.update(&io, &settings, &ScanResult { class: Class::Fighter, position: vec2(-4000.0, 2000.0), velocity: vec2(0.0, 300.0), rssi: 0.0, snr: 30.0 });
+    assert_eq!(track.position(), vec2(-4000.0, 2000.0));
+    assert_eq!(track.velocity(), vec2(0.0, 300.0));
+    assert_eq!(track.acceleration(), Vec2::zero());
+}
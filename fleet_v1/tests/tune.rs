@@ -0,0 +1,25 @@
+use std::process::Command;
+
+fn tune(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_tune")).args(args).output().unwrap()
+}
+
+#[test]
+fn tuning_is_reproducible_from_the_seed() {
+    let args = ["--seed", "3", "--iterations", "2", "--games", "1"];
+    let (first, second) = (tune(&args), tune(&args));
+    assert!(first.status.success(), "{}", String::from_utf8_lossy(&first.stderr));
+    assert_eq!(first.stdout, second.stdout);
+
+    let report = String::from_utf8(first.stdout).unwrap();
+    for line in ["defaults: score", "best: score", "missile vs target", "settings (changed from the defaults):"] {
+        assert!(report.contains(line), "missing `{}` in:\n{}", line, report);
+    }
+}
+
+#[test]
+fn bad_arguments_print_the_usage() {
+    let output = tune(&["--games", "many"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("usage: tune"));
+}
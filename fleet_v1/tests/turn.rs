@@ -0,0 +1,59 @@
+use oort_api::prelude::*;
+
+use fleet_v1::io::OortIo;
+use fleet_v1::navigation;
+use fleet_v1::sim::{Pilot, Simulation};
+
+const CLASSES: [Class; 5] = [Class::Fighter, Class::Frigate, Class::Cruiser, Class::Missile, Class::Torpedo];
+const TOLERANCE: f64 = 0.01; // rad
+
+// Turns a ship of the class towards a goal heading (a function of the time) and returns its heading error every tick
+fn turn(class: Class, goal: fn(f64) -> f64, angular_velocity: f64, seconds: f64) -> Vec<f64> {
+    let mut sim = Simulation::new(1);
+    let id = sim.add_ship_with_pilot(class, 0, vec2(0.0, 0.0), vec2(0.0, 0.0), 0.0, Pilot::Scripted(Box::new(move || {
+        let time = current_tick() as f64 * TICK_LENGTH;
+        let goal_rate = (goal(time + TICK_LENGTH) - goal(time)) / TICK_LENGTH;
+        navigation::turn(&mut OortIo, angle_diff(heading(), goal(time)), goal_rate);
+    })));
+    sim.ships.iter_mut().find(|ship| ship.id == id).unwrap().angular_velocity = angular_velocity;
+
+    let mut errors = Vec::new();
+    while sim.current_time() < seconds {
+        sim.tick();
+        errors.push(angle_diff(sim.ship(id).unwrap().heading, goal(sim.current_time())));
+    }
+    errors
+}
+
+fn settling_time(errors: &[f64]) -> f64 { // s until the error stays inside the tolerance
+    let settled = errors.iter().rposition(|error| error.abs() > TOLERANCE).map_or(0, |i| i + 1);
+    settled as f64 * TICK_LENGTH
+}
+
+#[test]
+fn turns_settle_in_near_optimal_time_without_overshoot() {
+    for class in CLASSES {
+        // rest to rest over a quarter turn takes at least 2 * sqrt(angle / max_angular_acceleration)
+        let optimal = 2.0 * (PI / 2.0 / class.default_stats().max_angular_acceleration).sqrt();
+        let errors = turn(class, |_| PI / 2.0, 0.0, 2.0 * optimal + 1.0);
+
+        let settling = settling_time(&errors);
+        assert!(settling <= optimal * 1.05 + 3.0 * TICK_LENGTH, "{:?} settled in {} s, optimal {} s", class, settling, optimal);
+        let overshoot = -errors.iter().cloned().fold(f64::INFINITY, f64::min); // the goal is ahead, past it the error turns negative
+        assert!(overshoot < TOLERANCE, "{:?} overshot by {} rad", class, overshoot);
+    }
+}
+
+#[test]
+fn turns_track_a_rotating_goal_against_the_current_spin() {
+    for class in CLASSES {
+        // spinning one way while the line of sight swings the other
+        let spin = 0.2 * class.default_stats().max_angular_acceleration.sqrt();
+        let errors = turn(class, |time| PI / 4.0 - 0.1 * time, spin, 30.0);
+        assert!(settling_time(&errors) < 20.0, "{:?} settled in {} s", class, settling_time(&errors));
+
+        // no oscillation on the way in: the error changes sign at most once
+        let sign_changes = errors.windows(2).filter(|pair| pair[0].abs() > TOLERANCE && pair[0].signum() != pair[1].signum()).count();
+        assert!(sign_changes <= 1, "{:?} crossed the goal {} times", class, sign_changes);
+    }
+}